@@ -2,38 +2,94 @@ use core::panic;
 use num_traits::{Float, Num, NumAssign, Signed};
 use std::fmt::{self, Debug};
 use std::ops::Add;
+use std::ops::AddAssign;
+use std::ops::Index;
+use std::ops::IndexMut;
 use std::ops::Mul;
+use std::ops::MulAssign;
+use std::ops::Neg;
 use std::ops::Sub;
+use std::ops::SubAssign;
 
-#[derive(PartialEq, Eq, Debug)]
-pub struct Matrix<
-    T: Num
-        + NumAssign
-        + Signed
-        + Float
-        + fmt::Display
-        + Copy
-        + PartialEq
-        + Debug
-        + std::iter::Product<T>,
-> {
+/// Equality compares dimensions and then every element with `==`, so two
+/// float matrices are only equal when their entries match exactly (and any
+/// `NaN` entry makes a matrix unequal to itself).
+#[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedMatrix<T>"))]
+pub struct Matrix<T: Num + NumAssign + Copy> {
     rows: usize,
     columns: usize,
     data: Vec<T>,
 }
 
-impl<
-        T: Num
-            + NumAssign
-            + Signed
-            + Float
-            + fmt::Display
-            + Copy
-            + PartialEq
-            + Debug
-            + std::iter::Product<T>,
-    > Matrix<T>
-{
+// Unchecked mirror of `Matrix` that deserialization goes through, so that
+// `data.len() == rows * columns` can be validated before building one
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedMatrix<T> {
+    rows: usize,
+    columns: usize,
+    data: Vec<T>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: Num + NumAssign + Copy> TryFrom<SerializedMatrix<T>> for Matrix<T> {
+    type Error = &'static str;
+
+    fn try_from(matrix: SerializedMatrix<T>) -> Result<Self, Self::Error> {
+        if matrix.data.len() != matrix.rows * matrix.columns {
+            return Err("Data is not the required size");
+        }
+
+        Ok(Matrix {
+            rows: matrix.rows,
+            columns: matrix.columns,
+            data: matrix.data,
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum MatrixError {
+    /// The operands' shapes don't fit together. Both shapes are given as
+    /// `(rows, columns)`; for multiplication `expected` is the shape the
+    /// right-hand side would need.
+    DimensionMismatch {
+        expected: (usize, usize),
+        got: (usize, usize),
+    },
+    /// A row index at or past the number of rows.
+    RowOutOfBounds { row: usize, rows: usize },
+    /// A column index at or past the number of columns.
+    ColumnOutOfBounds { column: usize, columns: usize },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MatrixError::DimensionMismatch { expected, got } => write!(
+                f,
+                "Matrix dimentions are inadecuate: expected {}x{}, got {}x{}.",
+                expected.0, expected.1, got.0, got.1
+            ),
+            MatrixError::RowOutOfBounds { row, rows } => write!(
+                f,
+                "Row index is out of bounds: got {}, but the matrix has {} rows.",
+                row, rows
+            ),
+            MatrixError::ColumnOutOfBounds { column, columns } => write!(
+                f,
+                "Column index is out of bounds: got {}, but the matrix has {} columns.",
+                column, columns
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+impl<T: Num + NumAssign + Copy> Matrix<T> {
     pub fn new(rows: usize, columns: usize, default: T) -> Self {
         Self {
             rows,
@@ -42,19 +98,178 @@ impl<
         }
     }
 
+    pub fn from_rows(data: Vec<Vec<T>>) -> Self {
+        let rows = data.len();
+        let columns = data.first().map_or(0, |row| row.len());
+        if data.iter().any(|row| row.len() != columns) {
+            panic!("All rows need to be the same size.");
+        }
+
+        Self {
+            rows,
+            columns,
+            data: data.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn from_2d<const R: usize, const C: usize>(data: [[T; C]; R]) -> Self {
+        Self {
+            rows: R,
+            columns: C,
+            data: data.into_iter().flatten().collect(),
+        }
+    }
+
+    pub fn zeros(rows: usize, columns: usize) -> Self {
+        Matrix::new(rows, columns, T::zero())
+    }
+
+    pub fn ones(rows: usize, columns: usize) -> Self {
+        Matrix::new(rows, columns, T::one())
+    }
+
+    pub fn identity(size: usize) -> Self {
+        Matrix::from_diagonal(vec![T::one(); size])
+    }
+
+    pub fn from_diagonal(values: Vec<T>) -> Self {
+        let size = values.len();
+        let mut matrix = Matrix::zeros(size, size);
+        for (i, value) in values.into_iter().enumerate() {
+            matrix.set(i, i, value);
+        }
+        matrix
+    }
+
+    /// Builds an `n x n` banded matrix from `(offset, values)` pairs, like
+    /// MATLAB's `spdiags`. Offset `0` is the main diagonal, positive offsets
+    /// are above it and negative ones below, and each diagonal needs exactly
+    /// `n - |offset|` values.
+    pub fn from_diagonals(diagonals: &[(isize, Vec<T>)], n: usize) -> Self {
+        let mut matrix = Matrix::zeros(n, n);
+        for (offset, values) in diagonals {
+            let distance = offset.unsigned_abs();
+            if distance >= n.max(1) || values.len() != n - distance {
+                panic!("Diagonal is not the required size");
+            }
+
+            for (i, value) in values.iter().enumerate() {
+                if *offset >= 0 {
+                    matrix.set(i, i + distance, *value);
+                } else {
+                    matrix.set(i + distance, i, *value);
+                }
+            }
+        }
+        matrix
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns `(rows, columns)`.
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.columns)
+    }
+
+    /// Bytes used by the matrix: the allocated capacity of the element
+    /// buffer plus the struct itself.
+    pub fn memory_bytes(&self) -> usize {
+        std::mem::size_of::<Self>() + self.data.capacity() * std::mem::size_of::<T>()
+    }
+
+    /// Reserves room for at least `additional_elements` more elements, to
+    /// avoid reallocating while growing the matrix.
+    pub fn reserve(&mut self, additional_elements: usize) {
+        self.data.reserve(additional_elements);
+    }
+
+    /// Releases any spare capacity of the element buffer.
+    pub fn shrink_to_fit(&mut self) {
+        self.data.shrink_to_fit();
+    }
+
+    /// The backing storage, in row-major order: element `(i, j)` is at
+    /// index `i * columns() + j`.
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Owned copy of the row-major data, laid out as in `as_slice`.
+    pub fn to_vec(&self) -> Vec<T> {
+        self.data.clone()
+    }
+
+    /// Iterates over the elements in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    /// Iterates over the rows as slices, without allocating like `get_row`.
+    pub fn iter_rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.rows).map(move |i| &self.data[i * self.columns..(i + 1) * self.columns])
+    }
+
     pub fn get(&self, row: usize, column: usize) -> &T {
+        match self.try_get(row, column) {
+            Some(value) => value,
+            None => panic!("Index given is out of range."),
+        }
+    }
+
+    pub fn try_get(&self, row: usize, column: usize) -> Option<&T> {
         if row >= self.rows || column >= self.columns {
-            panic!("Index given is out of range.")
+            return None;
         }
         let mut index = 0;
         index += row * self.columns;
         index += column;
-        return &self.data[index];
+        Some(&self.data[index])
+    }
+
+    pub fn get_mut(&mut self, row: usize, column: usize) -> &mut T {
+        if row >= self.rows || column >= self.columns {
+            panic!("Index given is out of range.")
+        }
+        let index = row * self.columns + column;
+        &mut self.data[index]
+    }
+
+    /// Like `get`, but without the bounds check.
+    ///
+    /// # Safety
+    ///
+    /// `row` must be less than `rows()` and `column` less than `columns()`;
+    /// anything else is undefined behavior, even if the flat index would
+    /// still land inside the data.
+    pub unsafe fn get_unchecked(&self, row: usize, column: usize) -> &T {
+        self.data.get_unchecked(row * self.columns + column)
+    }
+
+    /// Like `get_mut`, but without the bounds check.
+    ///
+    /// # Safety
+    ///
+    /// Same contract as `get_unchecked`.
+    pub unsafe fn get_unchecked_mut(&mut self, row: usize, column: usize) -> &mut T {
+        self.data.get_unchecked_mut(row * self.columns + column)
     }
 
     pub fn get_row(&self, row: usize) -> Vec<T> {
+        match self.try_get_row(row) {
+            Some(data) => data,
+            None => panic!("Row index is out of bounds."),
+        }
+    }
+
+    pub fn try_get_row(&self, row: usize) -> Option<Vec<T>> {
         if row >= self.rows {
-            panic!("Row index is out of bounds.");
+            return None;
         }
 
         let mut index = 0;
@@ -65,12 +280,19 @@ impl<
             data.push(self.data[index + i]);
         }
 
-        return data;
+        Some(data)
     }
 
     pub fn get_column(&self, column: usize) -> Vec<T> {
+        match self.try_get_column(column) {
+            Some(data) => data,
+            None => panic!("Column index is out of bounds."),
+        }
+    }
+
+    pub fn try_get_column(&self, column: usize) -> Option<Vec<T>> {
         if column >= self.columns {
-            panic!("Column index is out of bounds.");
+            return None;
         }
 
         let index = column;
@@ -80,7 +302,7 @@ impl<
             data.push(self.data[(i * self.columns) + index])
         }
 
-        return data;
+        Some(data)
     }
 
     pub fn get_diagonal(&self) -> Vec<T> {
@@ -90,15 +312,20 @@ impl<
 
         let mut data = Vec::new();
 
-        let mut index = 0;
         for i in 0..self.columns {
-            index = i + (i * self.columns);
+            let index = i + (i * self.columns);
             data.push(self.data[index]);
         }
-        return data;
+        data
     }
 
-    pub fn set(&mut self, row: usize, column: usize, data: T) -> () {
+    pub fn trace(&self) -> T {
+        self.get_diagonal()
+            .into_iter()
+            .fold(T::zero(), |sum, value| sum + value)
+    }
+
+    pub fn set(&mut self, row: usize, column: usize, data: T) {
         if row >= self.rows || column >= self.columns {
             panic!("Index given is out of range.")
         }
@@ -106,10 +333,9 @@ impl<
         index += row * self.columns;
         index += column;
         self.data[index] = data;
-        return;
     }
 
-    pub fn set_row(&mut self, row: usize, data: Vec<T>) -> () {
+    pub fn set_row(&mut self, row: usize, data: Vec<T>) {
         if row >= self.rows {
             panic!("Row index given is out of bounds.")
         }
@@ -117,12 +343,12 @@ impl<
             panic!("Data is not the required size")
         }
 
-        for i in 0..data.len() {
-            self.set(row, i, data[i]);
+        for (i, value) in data.into_iter().enumerate() {
+            self.set(row, i, value);
         }
     }
 
-    pub fn set_column(&mut self, column: usize, data: Vec<T>) -> () {
+    pub fn set_column(&mut self, column: usize, data: Vec<T>) {
         if column >= self.columns {
             panic!("Column index given is out of bouds.")
         }
@@ -130,14 +356,96 @@ impl<
             panic!("Data is not the required size")
         }
 
-        for i in 0..data.len() {
-            self.set(i, column, data[i]);
+        for (i, value) in data.into_iter().enumerate() {
+            self.set(i, column, value);
         }
     }
 
-    pub fn exchange_rows(&mut self, row1: usize, row2: usize) -> () {
-        if row1 >= self.rows || row2 >= self.rows {
-            panic!("Row index is out of bounds.");
+    /// Appends a row. An empty matrix takes its number of columns from the
+    /// first row pushed.
+    pub fn push_row(&mut self, data: Vec<T>) {
+        if self.rows == 0 {
+            self.columns = data.len();
+        } else if data.len() != self.columns {
+            panic!("Data is not the required size");
+        }
+
+        self.data.extend(data);
+        self.rows += 1;
+    }
+
+    /// Appends a column, re-laying out the data for the wider rows. An empty
+    /// matrix takes its number of rows from the first column pushed.
+    pub fn push_column(&mut self, data: Vec<T>) {
+        if self.columns == 0 {
+            self.rows = data.len();
+        } else if data.len() != self.rows {
+            panic!("Data is not the required size");
+        }
+
+        let columns = self.columns + 1;
+        let mut new_data = Vec::with_capacity(self.rows * columns);
+        for (i, value) in data.into_iter().enumerate() {
+            new_data.extend_from_slice(&self.data[i * self.columns..(i + 1) * self.columns]);
+            new_data.push(value);
+        }
+        self.data = new_data;
+        self.columns = columns;
+    }
+
+    /// Shifts the rows cyclically like numpy's `roll`: row `i` moves to row
+    /// `i + shift` (modulo the number of rows), so negative shifts go up.
+    pub fn roll_rows(&self, shift: isize) -> Matrix<T> {
+        let mut matrix = self.clone();
+        if self.rows > 0 {
+            let offset = shift.rem_euclid(self.rows as isize) as usize;
+            matrix.data.rotate_right(offset * self.columns);
+        }
+        matrix
+    }
+
+    /// Column counterpart of `roll_rows`: column `k` moves to column
+    /// `k + shift` (modulo the number of columns).
+    pub fn roll_columns(&self, shift: isize) -> Matrix<T> {
+        let mut matrix = self.clone();
+        if self.columns > 0 {
+            let offset = shift.rem_euclid(self.columns as isize) as usize;
+            for row in matrix.data.chunks_mut(self.columns) {
+                row.rotate_right(offset);
+            }
+        }
+        matrix
+    }
+
+    /// Reverses the order of the rows in place, so the first row ends up last.
+    pub fn reverse_rows(&mut self) {
+        for i in 0..self.rows / 2 {
+            self.exchange_rows(i, self.rows - 1 - i);
+        }
+    }
+
+    /// Reverses the order of the columns in place.
+    pub fn reverse_columns(&mut self) {
+        if self.columns > 0 {
+            for row in self.data.chunks_mut(self.columns) {
+                row.reverse();
+            }
+        }
+    }
+
+    pub fn exchange_rows(&mut self, row1: usize, row2: usize) {
+        self.try_exchange_rows(row1, row2)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_exchange_rows(&mut self, row1: usize, row2: usize) -> Result<(), MatrixError> {
+        for row in [row1, row2] {
+            if row >= self.rows {
+                return Err(MatrixError::RowOutOfBounds {
+                    row,
+                    rows: self.rows,
+                });
+            }
         }
 
         //Get copy of row2
@@ -147,622 +455,5548 @@ impl<
         //Move from row1 to row2
         index1 += self.columns * row1;
         index2 += self.columns * row2;
-        for i in 0..self.columns {
+        for (i, value) in temp.into_iter().enumerate() {
             self.data[index2 + i] = self.data[index1 + i];
-            self.data[index1 + i] = temp[i];
+            self.data[index1 + i] = value;
         }
+        Ok(())
+    }
+
+    pub fn exchange_columns(&mut self, column1: usize, column2: usize) {
+        self.try_exchange_columns(column1, column2)
+            .unwrap_or_else(|error| panic!("{}", error))
     }
 
-    pub fn exchange_columns(&mut self, column1: usize, column2: usize) -> () {
-        if column1 >= self.columns || column2 >= self.columns {
-            panic!("Column index is out of bounds.")
+    pub fn try_exchange_columns(
+        &mut self,
+        column1: usize,
+        column2: usize,
+    ) -> Result<(), MatrixError> {
+        for column in [column1, column2] {
+            if column >= self.columns {
+                return Err(MatrixError::ColumnOutOfBounds {
+                    column,
+                    columns: self.columns,
+                });
+            }
         }
 
         //Get copy of column2
         let temp = self.get_column(column2);
-        for i in 0..self.rows {
+        for (i, value) in temp.into_iter().enumerate() {
             self.data[column2 + (i * self.columns)] = self.data[column1 + (i * self.columns)];
-            self.data[column1 + (i * self.columns)] = temp[i];
+            self.data[column1 + (i * self.columns)] = value;
         }
+        Ok(())
     }
 
-    pub fn get_determinant(&self) -> T {
-        if self.rows != self.columns {
-            panic!("Only nxn matrixes can have a determinant.");
+    pub fn checked_add(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.rows != other.rows || self.columns != other.columns {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.rows, self.columns),
+                got: (other.rows, other.columns),
+            });
         }
 
-        let mut trig_matrix = Matrix {
-            columns: self.columns,
+        Ok(Matrix {
             rows: self.rows,
-            data: self.data.clone(),
-        };
-        let mut sign = T::one();
-
-        for i in 0..self.columns {
-            let mut pivot = *trig_matrix.get(i, i);
-
-            // Assign x to next row
-            let mut x = i + 1;
-            while pivot.is_zero() && x < self.rows {
-                if !trig_matrix.get(x, i).is_zero() {
-                    trig_matrix.exchange_rows(x, i);
-                    sign = -sign;
-                    pivot = *trig_matrix.get(i, i);
-                    break;
-                }
-                x += 1;
-            }
-            // If even exchanging in all ways posible pivot is still 0
-            // then determinant is 0
-            if pivot.is_zero() {
-                return T::zero();
-            }
+            columns: self.columns,
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(a, b)| *a + *b)
+                .collect(),
+        })
+    }
 
-            //So, we got the pivot, now we evaluate to 0 to create the
-            //triangular matrix
-            x = i + 1;
-            while x < trig_matrix.rows {
-                let m = *trig_matrix.get(x, i) / pivot;
-                let new_row = trig_matrix
-                    .get_row(x)
-                    .iter()
-                    .zip(trig_matrix.get_row(i).iter())
-                    .map(|(a, b)| *a - m * *b)
-                    .collect::<Vec<T>>();
-                trig_matrix.set_row(x, new_row);
-                x += 1;
-            }
+    pub fn checked_sub(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.rows != other.rows || self.columns != other.columns {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.rows, self.columns),
+                got: (other.rows, other.columns),
+            });
         }
 
-        // YES, now we got ourselves a triangular matrix, now we just
-        // take the product of the diagonal and multiply by sign, that's
-        // the determinant :)
-        let determinant = sign * trig_matrix.get_diagonal().iter().copied().product::<T>();
-
-        return determinant;
+        Ok(Matrix {
+            rows: self.rows,
+            columns: self.columns,
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(a, b)| *a - *b)
+                .collect(),
+        })
     }
-}
-
-impl<
-        T: Num
-            + NumAssign
-            + Signed
-            + Float
-            + fmt::Display
-            + Copy
-            + PartialEq
-            + Debug
-            + std::iter::Product<T>,
-    > Add for Matrix<T>
-{
-    type Output = Self;
 
-    fn add(self, other: Self) -> Self::Output {
-        if self.data.len() != other.data.len() {
-            panic!("Matrix size is inadecuate.");
+    pub fn checked_mul(&self, other: &Matrix<T>) -> Result<Matrix<T>, MatrixError> {
+        if self.columns != other.rows {
+            return Err(MatrixError::DimensionMismatch {
+                expected: (self.columns, other.columns),
+                got: (other.rows, other.columns),
+            });
         }
 
-        let mut new_data = Vec::new();
-        for i in 0..self.data.len() {
-            new_data.push(self.data[i] + other.data[i]);
+        let mut new_data = vec![T::zero(); self.rows * other.columns];
+        if other.columns > 0 {
+            for (i, output) in new_data.chunks_mut(other.columns).enumerate() {
+                self.accumulate_row_product(other, i, output);
+            }
         }
 
-        Matrix {
-            columns: self.columns,
+        Ok(Matrix {
             rows: self.rows,
+            columns: other.columns,
             data: new_data,
-        }
+        })
     }
-}
 
-impl<
-        T: Num
-            + NumAssign
-            + Signed
-            + Float
-            + fmt::Display
-            + Copy
-            + PartialEq
-            + Debug
-            + std::iter::Product<T>,
-    > Sub for Matrix<T>
-{
-    type Output = Self;
+    /// Writes `self * other` into the existing buffer of `out`, which must
+    /// already have the shape of the product.
+    pub fn mul_into(&self, other: &Matrix<T>, out: &mut Matrix<T>) {
+        if self.columns != other.rows {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.columns, other.columns),
+                    got: (other.rows, other.columns),
+                }
+            );
+        }
+        if out.rows != self.rows || out.columns != other.columns {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.rows, other.columns),
+                    got: (out.rows, out.columns),
+                }
+            );
+        }
 
-    fn sub(self, other: Self) -> Self::Output {
-        if self.data.len() != other.data.len() {
-            panic!("Matrix size is inadecuate.");
+        for value in out.data.iter_mut() {
+            *value = T::zero();
+        }
+        if other.columns > 0 {
+            for (i, output) in out.data.chunks_mut(other.columns).enumerate() {
+                self.accumulate_row_product(other, i, output);
+            }
         }
+    }
 
-        let mut new_data = Vec::new();
-        for i in 0..self.data.len() {
-            new_data.push(self.data[i] - other.data[i]);
+    /// BLAS-style `self = alpha * (a * b) + beta * self`, computed in place
+    /// one row at a time.
+    pub fn gemm(&mut self, alpha: T, a: &Matrix<T>, b: &Matrix<T>, beta: T) {
+        if a.columns != b.rows {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (a.columns, b.columns),
+                    got: (b.rows, b.columns),
+                }
+            );
+        }
+        if self.rows != a.rows || self.columns != b.columns {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (a.rows, b.columns),
+                    got: (self.rows, self.columns),
+                }
+            );
         }
 
-        Matrix {
-            columns: self.columns,
-            rows: self.rows,
-            data: new_data,
+        let mut product = vec![T::zero(); self.columns];
+        for i in 0..self.rows {
+            for value in product.iter_mut() {
+                *value = T::zero();
+            }
+            a.accumulate_row_product(b, i, &mut product);
+
+            let row = &mut self.data[i * self.columns..(i + 1) * self.columns];
+            for (value, p) in row.iter_mut().zip(product.iter()) {
+                *value = alpha * *p + beta * *value;
+            }
         }
     }
-}
-
-impl<
-        T: Num
-            + NumAssign
-            + Signed
-            + Float
-            + fmt::Display
-            + Copy
-            + PartialEq
-            + Debug
-            + std::iter::Product<T>,
-    > Mul for Matrix<T>
-{
-    type Output = Self;
 
-    fn mul(self, other: Self) -> Self::Output {
+    /// Same product as `*`, computed in `block x block` tiles so the working
+    /// set of each tile stays in cache. Edge tiles are simply smaller when
+    /// the dimensions aren't multiples of `block`.
+    pub fn mul_tiled(&self, other: &Matrix<T>, block: usize) -> Matrix<T> {
+        if block == 0 {
+            panic!("Tiles need a positive block size.");
+        }
         if self.columns != other.rows {
-            panic!("Matrix dimentions are inadecuate.");
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.columns, other.columns),
+                    got: (other.rows, other.columns),
+                }
+            );
         }
 
-        let mut new_data: Vec<T> = Vec::new();
-        for i in 0..self.rows {
-            let current_row = self.get_row(i);
+        let mut product = Matrix::zeros(self.rows, other.columns);
+        for row_start in (0..self.rows).step_by(block) {
+            let row_end = (row_start + block).min(self.rows);
+            for inner_start in (0..self.columns).step_by(block) {
+                let inner_end = (inner_start + block).min(self.columns);
+                for column_start in (0..other.columns).step_by(block) {
+                    let column_end = (column_start + block).min(other.columns);
 
-            for k in 0..other.columns {
-                let current_column = other.get_column(k);
-                let mut new_value = T::zero();
-                for (a, b) in current_row.iter().zip(current_column.iter()) {
-                    new_value += *a * *b;
+                    for i in row_start..row_end {
+                        for k in inner_start..inner_end {
+                            let a = self.data[i * self.columns + k];
+                            let other_row = &other.data
+                                [k * other.columns + column_start..k * other.columns + column_end];
+                            let output = &mut product.data
+                                [i * other.columns + column_start..i * other.columns + column_end];
+                            for (value, b) in output.iter_mut().zip(other_row.iter()) {
+                                *value += a * *b;
+                            }
+                        }
+                    }
                 }
-                new_data.push(new_value);
             }
         }
+        product
+    }
+
+    // Adds row `i` of `self * other` into `output`, in i-k-j order: each
+    // entry a_ik scales row k of `other`, so both operands are read
+    // contiguously and, unlike a loop over `other.get_column`, nothing is
+    // allocated per column. Every output entry still sums its products in
+    // increasing k, so results match the textbook dot-product order
+    fn accumulate_row_product(&self, other: &Matrix<T>, i: usize, output: &mut [T]) {
+        let row = &self.data[i * self.columns..(i + 1) * self.columns];
+        for (k, a) in row.iter().enumerate() {
+            let other_row = &other.data[k * other.columns..(k + 1) * other.columns];
+            for (value, b) in output.iter_mut().zip(other_row.iter()) {
+                *value += *a * *b;
+            }
+        }
+    }
+
+    pub fn mul_vec(&self, vec: &[T]) -> Vec<T> {
+        if vec.len() != self.columns {
+            panic!("Data is not the required size");
+        }
+
+        (0..self.rows)
+            .map(|i| {
+                let row = &self.data[i * self.columns..(i + 1) * self.columns];
+                row.iter()
+                    .zip(vec.iter())
+                    .fold(T::zero(), |sum, (a, b)| sum + *a * *b)
+            })
+            .collect()
+    }
+
+    /// Whether every entry below the diagonal is exactly zero.
+    pub fn is_upper_triangular(&self) -> bool {
+        (0..self.rows).all(|i| (0..i.min(self.columns)).all(|k| self.get(i, k).is_zero()))
+    }
+
+    /// Whether every entry above the diagonal is exactly zero.
+    pub fn is_lower_triangular(&self) -> bool {
+        (0..self.rows).all(|i| ((i + 1)..self.columns).all(|k| self.get(i, k).is_zero()))
+    }
+
+    /// Determinant by cofactor expansion along the first row. It needs no
+    /// division, so it's exact for integers and avoids the rounding of
+    /// elimination, but it takes O(n!) time: only use it for small matrices.
+    pub fn determinant_cofactor(&self) -> T {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can have a determinant.");
+        }
+
+        match self.rows {
+            0 => T::one(),
+            1 => self.data[0],
+            2 => self.data[0] * self.data[3] - self.data[1] * self.data[2],
+            _ => {
+                let minors = self.without_row(0);
+                let mut determinant = T::zero();
+                for (k, value) in self.data.iter().take(self.columns).enumerate() {
+                    let term = *value * minors.without_column(k).determinant_cofactor();
+                    if k % 2 == 0 {
+                        determinant += term;
+                    } else {
+                        determinant -= term;
+                    }
+                }
+                determinant
+            }
+        }
+    }
+
+    /// Matrix of cofactors `(-1)^(i+j) M_ij`, with each minor `M_ij`
+    /// computed by `determinant_cofactor`.
+    pub fn cofactor_matrix(&self) -> Matrix<T> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes have a cofactor matrix.");
+        }
+
+        let mut cofactors = Matrix::zeros(self.rows, self.columns);
+        for i in 0..self.rows {
+            let without_row = self.without_row(i);
+            for k in 0..self.columns {
+                let minor = without_row.without_column(k).determinant_cofactor();
+                cofactors.data[i * self.columns + k] = if (i + k) % 2 == 0 {
+                    minor
+                } else {
+                    T::zero() - minor
+                };
+            }
+        }
+        cofactors
+    }
+
+    /// Transpose of the cofactor matrix, so that `A adj(A) = det(A) I`.
+    pub fn adjugate(&self) -> Matrix<T> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes have an adjugate.");
+        }
+
+        self.cofactor_matrix().transpose()
+    }
+
+    /// Integer power by repeated squaring, so it takes O(log exponent)
+    /// multiplications. `pow(0)` is the identity.
+    pub fn pow(&self, exponent: u32) -> Matrix<T> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be raised to a power.");
+        }
+
+        let mut result = Matrix::identity(self.rows);
+        let mut base = self.clone();
+        let mut exponent = exponent;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    pub fn row_dot(&self, row_a: usize, row_b: usize) -> T {
+        if row_a >= self.rows || row_b >= self.rows {
+            panic!("Row index is out of bounds.");
+        }
+
+        let a = &self.data[row_a * self.columns..(row_a + 1) * self.columns];
+        let b = &self.data[row_b * self.columns..(row_b + 1) * self.columns];
+        a.iter()
+            .zip(b.iter())
+            .fold(T::zero(), |sum, (x, y)| sum + *x * *y)
+    }
+
+    pub fn column_dot(&self, col_a: usize, col_b: usize) -> T {
+        if col_a >= self.columns || col_b >= self.columns {
+            panic!("Column index is out of bounds.");
+        }
+
+        (0..self.rows).fold(T::zero(), |sum, i| {
+            sum + self.data[i * self.columns + col_a] * self.data[i * self.columns + col_b]
+        })
+    }
+
+    /// Adds the outer product `v vᵀ` in place, e.g. to build up a scatter
+    /// matrix one sample at a time.
+    pub fn accumulate_outer(&mut self, v: &[T]) {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can accumulate outer products.");
+        }
+        if v.len() != self.rows {
+            panic!("Data is not the required size");
+        }
+
+        for (i, a) in v.iter().enumerate() {
+            let row = &mut self.data[i * self.columns..(i + 1) * self.columns];
+            for (entry, b) in row.iter_mut().zip(v.iter()) {
+                *entry += *a * *b;
+            }
+        }
+    }
+
+    /// Element-wise (Hadamard) product. Unlike `*` this needs both matrices
+    /// to have the same shape.
+    pub fn hadamard(&self, other: &Matrix<T>) -> Matrix<T> {
+        if self.rows != other.rows || self.columns != other.columns {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.rows, self.columns),
+                    got: (other.rows, other.columns),
+                }
+            );
+        }
 
         Matrix {
             rows: self.rows,
-            columns: other.columns,
-            data: new_data,
+            columns: self.columns,
+            data: self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .map(|(a, b)| *a * *b)
+                .collect(),
+        }
+    }
+
+    pub fn scale(&self, factor: T) -> Matrix<T> {
+        Matrix {
+            rows: self.rows,
+            columns: self.columns,
+            data: self.data.iter().map(|value| *value * factor).collect(),
+        }
+    }
+
+    pub fn add_scalar(&self, value: T) -> Matrix<T> {
+        self.map(|element| element + value)
+    }
+
+    pub fn sub_scalar(&self, value: T) -> Matrix<T> {
+        self.map(|element| element - value)
+    }
+
+    pub fn transpose(&self) -> Matrix<T> {
+        let mut data = Vec::with_capacity(self.data.len());
+        for i in 0..self.columns {
+            data.extend(self.get_column(i));
+        }
+
+        Matrix {
+            rows: self.columns,
+            columns: self.rows,
+            data,
+        }
+    }
+
+    /// Transposes a square matrix without allocating, by swapping the
+    /// entries across the diagonal. Rectangular matrices need `transpose`.
+    pub fn transpose_in_place(&mut self) {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be transposed in place.");
+        }
+
+        let n = self.rows;
+        for i in 0..n {
+            for j in (i + 1)..n {
+                self.data.swap(i * n + j, j * n + i);
+            }
+        }
+    }
+
+    /// Sylvester's Hadamard matrix of the given order, whose rows are
+    /// mutually orthogonal ±1 vectors. Sylvester's construction only exists
+    /// for powers of two, so any other order returns `None`.
+    pub fn hadamard_matrix(order: usize) -> Option<Matrix<T>> {
+        if !order.is_power_of_two() {
+            return None;
+        }
+
+        // H(2n) = [[H(n), H(n)], [H(n), -H(n)]] unrolls to a sign given by
+        // the parity of the bits shared by the row and column indices
+        let minus_one = T::zero() - T::one();
+        let mut matrix = Matrix::zeros(order, order);
+        for i in 0..order {
+            for k in 0..order {
+                let value = if (i & k).count_ones() % 2 == 0 {
+                    T::one()
+                } else {
+                    minus_one
+                };
+                matrix.set(i, k, value);
+            }
+        }
+        Some(matrix)
+    }
+
+    /// Reinterprets the row-major data as a `rows x columns` matrix without
+    /// moving any element.
+    pub fn reshape(&mut self, rows: usize, columns: usize) {
+        if rows * columns != self.data.len() {
+            panic!("Data is not the required size");
+        }
+
+        self.rows = rows;
+        self.columns = columns;
+    }
+
+    pub fn reshaped(&self, rows: usize, columns: usize) -> Matrix<T> {
+        let mut matrix = self.clone();
+        matrix.reshape(rows, columns);
+        matrix
+    }
+
+    /// Copies the block covering rows `[row_start, row_end)` and columns
+    /// `[col_start, col_end)`.
+    pub fn submatrix(
+        &self,
+        row_start: usize,
+        row_end: usize,
+        col_start: usize,
+        col_end: usize,
+    ) -> Matrix<T> {
+        if row_start > row_end || row_end > self.rows {
+            panic!("Row index is out of bounds.");
+        }
+        if col_start > col_end || col_end > self.columns {
+            panic!("Column index is out of bounds.");
+        }
+
+        let mut data = Vec::with_capacity((row_end - row_start) * (col_end - col_start));
+        for i in row_start..row_end {
+            data.extend_from_slice(
+                &self.data[i * self.columns + col_start..i * self.columns + col_end],
+            );
+        }
+        Matrix {
+            rows: row_end - row_start,
+            columns: col_end - col_start,
+            data,
+        }
+    }
+
+    /// Gathers the listed rows and columns, in the given order, into a new
+    /// matrix. Indices may repeat.
+    pub fn select(&self, rows: &[usize], columns: &[usize]) -> Matrix<T> {
+        if rows.iter().any(|row| *row >= self.rows) {
+            panic!("Row index is out of bounds.");
+        }
+        if columns.iter().any(|column| *column >= self.columns) {
+            panic!("Column index is out of bounds.");
+        }
+
+        let mut data = Vec::with_capacity(rows.len() * columns.len());
+        for row in rows {
+            for column in columns {
+                data.push(self.data[row * self.columns + column]);
+            }
+        }
+        Matrix {
+            rows: rows.len(),
+            columns: columns.len(),
+            data,
+        }
+    }
+
+    /// Applies the same permutation to rows and columns, `P A Pᵀ`, where
+    /// row `i` of the result is row `perm[i]` of `A`. Symmetric matrices
+    /// stay symmetric.
+    pub fn permute_symmetric(&self, perm: &[usize]) -> Matrix<T> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be permuted symmetrically.");
+        }
+        if perm.len() != self.rows {
+            panic!("Data is not the required size");
+        }
+        let mut seen = vec![false; self.rows];
+        for i in perm {
+            if *i >= self.rows || seen[*i] {
+                panic!("Data is not a permutation of the indices.");
+            }
+            seen[*i] = true;
+        }
+
+        self.select(perm, perm)
+    }
+
+    /// Graph Laplacian `D - A` of a symmetric adjacency matrix, with `D` the
+    /// diagonal matrix of row sums (vertex degrees).
+    pub fn graph_laplacian(&self) -> Matrix<T> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be adjacency matrixes.");
+        }
+
+        let mut laplacian = Matrix::zeros(self.rows, self.columns);
+        for i in 0..self.rows {
+            let row = &self.data[i * self.columns..(i + 1) * self.columns];
+            let degree = row.iter().fold(T::zero(), |sum, value| sum + *value);
+            for (j, value) in row.iter().enumerate() {
+                laplacian.data[i * self.columns + j] = T::zero() - *value;
+            }
+            laplacian.data[i * self.columns + i] += degree;
+        }
+        laplacian
+    }
+
+    pub fn without_row(&self, row: usize) -> Matrix<T> {
+        if row >= self.rows {
+            panic!("Row index is out of bounds.");
+        }
+
+        let mut data = self.data.clone();
+        data.drain(row * self.columns..(row + 1) * self.columns);
+        Matrix {
+            rows: self.rows - 1,
+            columns: self.columns,
+            data,
+        }
+    }
+
+    pub fn without_column(&self, column: usize) -> Matrix<T> {
+        if column >= self.columns {
+            panic!("Column index is out of bounds.");
+        }
+
+        let data = self
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(index, _value)| index % self.columns != column)
+            .map(|(_index, value)| *value)
+            .collect();
+        Matrix {
+            rows: self.rows,
+            columns: self.columns - 1,
+            data,
+        }
+    }
+
+    /// Kronecker product: every entry `a_ij` is replaced by the block
+    /// `a_ij * other`, giving a `(m p) x (n q)` matrix.
+    pub fn kronecker(&self, other: &Matrix<T>) -> Matrix<T> {
+        let rows = self.rows * other.rows;
+        let columns = self.columns * other.columns;
+        let mut data = Vec::with_capacity(rows * columns);
+        for i in 0..self.rows {
+            for p in 0..other.rows {
+                for j in 0..self.columns {
+                    let a = self.data[i * self.columns + j];
+                    let block_row = &other.data[p * other.columns..(p + 1) * other.columns];
+                    data.extend(block_row.iter().map(|b| a * *b));
+                }
+            }
+        }
+        Matrix {
+            rows,
+            columns,
+            data,
+        }
+    }
+
+    pub fn vstack(&self, other: &Matrix<T>) -> Matrix<T> {
+        if self.columns != other.columns {
+            panic!("Matrixes need the same number of columns to stack vertically.");
+        }
+
+        let mut data = self.data.clone();
+        data.extend_from_slice(&other.data);
+        Matrix {
+            rows: self.rows + other.rows,
+            columns: self.columns,
+            data,
+        }
+    }
+
+    pub fn hstack(&self, other: &Matrix<T>) -> Matrix<T> {
+        if self.rows != other.rows {
+            panic!("Matrixes need the same number of rows to stack horizontally.");
+        }
+
+        let mut data = Vec::with_capacity(self.data.len() + other.data.len());
+        for i in 0..self.rows {
+            data.extend_from_slice(&self.data[i * self.columns..(i + 1) * self.columns]);
+            data.extend_from_slice(&other.data[i * other.columns..(i + 1) * other.columns]);
+        }
+        Matrix {
+            rows: self.rows,
+            columns: self.columns + other.columns,
+            data,
+        }
+    }
+
+    pub fn map<F: Fn(T) -> T>(&self, f: F) -> Matrix<T> {
+        Matrix {
+            rows: self.rows,
+            columns: self.columns,
+            data: self.data.iter().map(|value| f(*value)).collect(),
+        }
+    }
+
+    pub fn map_in_place<F: Fn(T) -> T>(&mut self, f: F) {
+        for value in self.data.iter_mut() {
+            *value = f(*value);
+        }
+    }
+}
+
+impl<
+        T: Num
+            + NumAssign
+            + Signed
+            + Float
+            + fmt::Display
+            + Copy
+            + PartialEq
+            + Debug
+            + std::iter::Product<T>,
+    > Matrix<T>
+{
+    pub fn get_determinant(&self) -> T {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can have a determinant.");
+        }
+
+        let mut trig_matrix = self.clone();
+        let mut sign = T::one();
+
+        for i in 0..self.columns {
+            let mut pivot = *trig_matrix.get(i, i);
+
+            // Assign x to next row. A zero pivot is only ever resolved by a
+            // row exchange (columns are never exchanged), and each exchange
+            // flips the sign of the determinant
+            let mut x = i + 1;
+            while pivot.is_zero() && x < self.rows {
+                if !trig_matrix.get(x, i).is_zero() {
+                    trig_matrix.exchange_rows(x, i);
+                    sign = -sign;
+                    pivot = *trig_matrix.get(i, i);
+                    break;
+                }
+                x += 1;
+            }
+            // If even exchanging in all ways posible pivot is still 0
+            // then determinant is 0
+            if pivot.is_zero() {
+                return T::zero();
+            }
+
+            //So, we got the pivot, now we evaluate to 0 to create the
+            //triangular matrix
+            x = i + 1;
+            while x < trig_matrix.rows {
+                let m = *trig_matrix.get(x, i) / pivot;
+                let new_row = trig_matrix
+                    .get_row(x)
+                    .iter()
+                    .zip(trig_matrix.get_row(i).iter())
+                    .map(|(a, b)| *a - m * *b)
+                    .collect::<Vec<T>>();
+                trig_matrix.set_row(x, new_row);
+                x += 1;
+            }
+        }
+
+        // YES, now we got ourselves a triangular matrix, now we just
+        // take the product of the diagonal and multiply by sign, that's
+        // the determinant :)
+        sign * trig_matrix.get_diagonal().iter().copied().product::<T>()
+    }
+
+    /// Inverts the matrix by Gauss-Jordan elimination on `[A | I]` with
+    /// partial pivoting. Returns `None` when the matrix is singular.
+    pub fn inverse(&self) -> Option<Matrix<T>> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can have an inverse.");
         }
+
+        let n = self.rows;
+        let tolerance = self.suggested_tolerance();
+        let mut reduced = self.clone();
+        let mut inverse = Matrix::identity(n);
+
+        for i in 0..n {
+            // Pick the row with the largest pivot candidate to keep the
+            // elimination stable
+            let mut pivot_row = i;
+            for x in (i + 1)..n {
+                if reduced.get(x, i).abs() > reduced.get(pivot_row, i).abs() {
+                    pivot_row = x;
+                }
+            }
+            if reduced.get(pivot_row, i).abs() <= tolerance {
+                return None;
+            }
+            if pivot_row != i {
+                reduced.exchange_rows(pivot_row, i);
+                inverse.exchange_rows(pivot_row, i);
+            }
+
+            let pivot = *reduced.get(i, i);
+            for k in 0..n {
+                reduced.data[i * n + k] /= pivot;
+                inverse.data[i * n + k] /= pivot;
+            }
+
+            for x in 0..n {
+                if x == i {
+                    continue;
+                }
+                let m = *reduced.get(x, i);
+                if m.is_zero() {
+                    continue;
+                }
+                for k in 0..n {
+                    let reduced_value = reduced.data[i * n + k];
+                    let inverse_value = inverse.data[i * n + k];
+                    reduced.data[x * n + k] -= m * reduced_value;
+                    inverse.data[x * n + k] -= m * inverse_value;
+                }
+            }
+        }
+
+        Some(inverse)
+    }
+
+    /// LU decomposition with partial pivoting, `P A = L U` with `L` unit
+    /// lower triangular and `U` upper triangular. The permutation is given as
+    /// the original row index of each row of `P A`. Returns `None` when the
+    /// matrix isn't square or is singular.
+    pub fn lu_decompose(&self) -> Option<(Matrix<T>, Matrix<T>, Vec<usize>)> {
+        if self.rows != self.columns {
+            return None;
+        }
+
+        let n = self.rows;
+        let tolerance = self.suggested_tolerance();
+        let mut upper = self.clone();
+        let mut lower = Matrix::zeros(n, n);
+        let mut permutation: Vec<usize> = (0..n).collect();
+
+        for i in 0..n {
+            let mut pivot_row = i;
+            for x in (i + 1)..n {
+                if upper.get(x, i).abs() > upper.get(pivot_row, i).abs() {
+                    pivot_row = x;
+                }
+            }
+            if upper.get(pivot_row, i).abs() <= tolerance {
+                return None;
+            }
+            if pivot_row != i {
+                upper.exchange_rows(pivot_row, i);
+                lower.exchange_rows(pivot_row, i);
+                permutation.swap(pivot_row, i);
+            }
+
+            let pivot = *upper.get(i, i);
+            for x in (i + 1)..n {
+                let m = *upper.get(x, i) / pivot;
+                *lower.get_mut(x, i) = m;
+                *upper.get_mut(x, i) = T::zero();
+                for k in (i + 1)..n {
+                    let value = upper.data[i * n + k];
+                    upper.data[x * n + k] -= m * value;
+                }
+            }
+        }
+
+        for i in 0..n {
+            *lower.get_mut(i, i) = T::one();
+        }
+
+        Some((lower, upper, permutation))
+    }
+
+    /// Solves `A x = b` for a square `A` from its LU decomposition, with a
+    /// forward substitution on `L y = P b` and a back substitution on
+    /// `U x = y`. Returns `None` when `A` is singular.
+    pub fn solve(&self, b: &[T]) -> Option<Vec<T>> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be solved.");
+        }
+        if b.len() != self.rows {
+            panic!("Data is not the required size");
+        }
+
+        let (lower, upper, permutation) = self.lu_decompose()?;
+        let n = self.rows;
+
+        let mut y = vec![T::zero(); n];
+        for i in 0..n {
+            let mut value = b[permutation[i]];
+            for (k, yk) in y.iter().enumerate().take(i) {
+                value -= *lower.get(i, k) * *yk;
+            }
+            y[i] = value;
+        }
+
+        let mut x = vec![T::zero(); n];
+        for i in (0..n).rev() {
+            let mut value = y[i];
+            for (k, xk) in x.iter().enumerate().skip(i + 1) {
+                value -= *upper.get(i, k) * *xk;
+            }
+            x[i] = value / *upper.get(i, i);
+        }
+
+        Some(x)
+    }
+
+    /// Thin QR decomposition `A = Q R` of a tall (rows >= columns) matrix by
+    /// modified Gram-Schmidt, with `Q` having orthonormal columns and `R`
+    /// square upper triangular. Returns `None` for wide matrices or when the
+    /// columns are linearly dependent.
+    pub fn qr_decompose(&self) -> Option<(Matrix<T>, Matrix<T>)> {
+        if self.rows < self.columns {
+            return None;
+        }
+
+        let tolerance = self.suggested_tolerance();
+        let mut q = self.clone();
+        let mut r = Matrix::zeros(self.columns, self.columns);
+        for j in 0..self.columns {
+            let norm = q
+                .get_column(j)
+                .iter()
+                .fold(T::zero(), |sum, value| sum + *value * *value)
+                .sqrt();
+            if norm <= tolerance {
+                return None;
+            }
+            r.set(j, j, norm);
+            for i in 0..self.rows {
+                *q.get_mut(i, j) /= norm;
+            }
+
+            for k in (j + 1)..self.columns {
+                let mut projection = T::zero();
+                for i in 0..self.rows {
+                    projection += *q.get(i, j) * *q.get(i, k);
+                }
+                r.set(j, k, projection);
+                for i in 0..self.rows {
+                    let value = *q.get(i, j);
+                    *q.get_mut(i, k) -= projection * value;
+                }
+            }
+        }
+
+        Some((q, r))
+    }
+
+    pub fn frobenius_norm(&self) -> T {
+        self.data
+            .iter()
+            .fold(T::zero(), |sum, value| sum + *value * *value)
+            .sqrt()
+    }
+
+    /// Gradient clipping: scales the matrix down so its Frobenius norm is
+    /// exactly `max_norm` if it exceeds it, otherwise returns it unchanged.
+    pub fn clip_norm(&self, max_norm: T) -> Matrix<T> {
+        let norm = self.frobenius_norm();
+        if norm > max_norm {
+            self.scale(max_norm / norm)
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Indices of the columns whose entries all lie within `tolerance` of
+    /// each other, i.e. features that would break standardization.
+    pub fn constant_columns(&self, tolerance: T) -> Vec<usize> {
+        (0..self.columns)
+            .filter(|k| is_constant(&self.get_column(*k), tolerance))
+            .collect()
+    }
+
+    /// Row counterpart of `constant_columns`.
+    pub fn constant_rows(&self, tolerance: T) -> Vec<usize> {
+        (0..self.rows)
+            .filter(|i| {
+                is_constant(
+                    &self.data[i * self.columns..(i + 1) * self.columns],
+                    tolerance,
+                )
+            })
+            .collect()
+    }
+
+    pub fn approx_eq(&self, other: &Matrix<T>, tolerance: T) -> bool {
+        self.rows == other.rows
+            && self.columns == other.columns
+            && self
+                .data
+                .iter()
+                .zip(other.data.iter())
+                .all(|(a, b)| (*a - *b).abs() <= tolerance)
+    }
+
+    /// Element-wise `|self - reference| / (|reference| + ε)`, with `ε` the
+    /// machine epsilon keeping entries where the reference is zero finite.
+    pub fn relative_error(&self, reference: &Matrix<T>) -> Matrix<T> {
+        if self.rows != reference.rows || self.columns != reference.columns {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.rows, self.columns),
+                    got: (reference.rows, reference.columns),
+                }
+            );
+        }
+
+        Matrix {
+            rows: self.rows,
+            columns: self.columns,
+            data: self
+                .data
+                .iter()
+                .zip(reference.data.iter())
+                .map(|(a, b)| (*a - *b).abs() / (b.abs() + T::epsilon()))
+                .collect(),
+        }
+    }
+
+    /// Real and imaginary parts of the `n x n` DFT matrix
+    /// `F[j][k] = e^(-2πi jk / n)`, i.e. `cos(2π jk / n)` and
+    /// `-sin(2π jk / n)`. Multiplying a real signal by each gives the real
+    /// and imaginary components of its spectrum.
+    pub fn dft_real(n: usize) -> (Matrix<T>, Matrix<T>) {
+        let mut real = Matrix::zeros(n, n);
+        let mut imaginary = Matrix::zeros(n, n);
+        let tau = T::from(std::f64::consts::TAU).unwrap();
+        for j in 0..n {
+            for k in 0..n {
+                // Reduce jk mod n first so large sizes keep their precision
+                let angle = tau * T::from((j * k) % n).unwrap() / T::from(n).unwrap();
+                real.set(j, k, angle.cos());
+                imaginary.set(j, k, -angle.sin());
+            }
+        }
+        (real, imaginary)
+    }
+
+    /// Approximates the inverse with the first `terms` terms of the Neumann
+    /// series `I + (I - A) + (I - A)^2 + ...`.
+    ///
+    /// The series only converges to `A^-1` when every eigenvalue of `I - A`
+    /// has magnitude below one, i.e. when `A` is close to the identity. For
+    /// other matrices the partial sums drift away as `terms` grows.
+    pub fn inverse_neumann(&self, terms: usize) -> Matrix<T> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can have an inverse.");
+        }
+
+        let identity = Matrix::identity(self.rows);
+        let difference = &identity - self;
+
+        // Horner's scheme: S(k+1) = I + (I - A) * S(k)
+        let mut sum = Matrix::zeros(self.rows, self.columns);
+        for _ in 0..terms {
+            sum = &(&difference * &sum) + &identity;
+        }
+
+        sum
+    }
+
+    /// Returns `||QᵀQ - I||` (Frobenius norm), which is zero exactly when
+    /// the columns of the matrix are orthonormal.
+    pub fn orthogonality_defect(&self) -> T {
+        let identity = Matrix::identity(self.columns);
+        let gram = &self.transpose() * self;
+
+        gram.frobenius_distance(&identity)
+    }
+
+    /// Whether the matrix is square and `a_ij == a_ji` up to the suggested
+    /// tolerance for float noise.
+    pub fn is_symmetric(&self) -> bool {
+        if self.rows != self.columns {
+            return false;
+        }
+
+        let tolerance = self.suggested_tolerance();
+        (0..self.rows).all(|i| {
+            ((i + 1)..self.columns).all(|k| (*self.get(i, k) - *self.get(k, i)).abs() <= tolerance)
+        })
+    }
+
+    /// Returns `||A - Aᵀ||` (Frobenius norm), zero exactly when the matrix
+    /// is symmetric.
+    pub fn symmetry_error(&self) -> T {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be symmetric.");
+        }
+
+        self.frobenius_distance(&self.transpose())
+    }
+
+    /// Applies one Newton-Schulz step `Q(1.5I - 0.5QᵀQ)`, pulling a nearly
+    /// orthogonal matrix back towards the closest orthogonal one. The step
+    /// only converges when the matrix is already close to orthogonal.
+    pub fn reorthogonalize(&self) -> Matrix<T> {
+        let half = T::one() / (T::one() + T::one());
+        let gram = &self.transpose() * self;
+        let correction = Matrix::identity(self.columns).scale(T::one() + half) - gram.scale(half);
+
+        self * &correction
+    }
+
+    /// Computes the geometric mean `A^(1/2) (A^(-1/2) B A^(-1/2))^(1/2) A^(1/2)`
+    /// of two symmetric positive-definite matrices, the midpoint of the
+    /// geodesic between them. Returns `None` if either matrix isn't SPD.
+    pub fn spd_geometric_mean(&self, other: &Matrix<T>) -> Option<Matrix<T>> {
+        if self.rows != other.rows || self.columns != other.columns {
+            panic!("Matrix size is inadecuate.");
+        }
+
+        other.spd_function(|x| x)?;
+        let sqrt = self.sqrt_spd()?;
+        let inverse_sqrt = self.spd_function(|x| x.sqrt().recip())?;
+
+        let inner = &(&inverse_sqrt * other) * &inverse_sqrt;
+        let inner_sqrt = inner.symmetrized().sqrt_spd()?;
+        let mean = &(&sqrt * &inner_sqrt) * &sqrt;
+
+        Some(mean.symmetrized())
+    }
+
+    /// Principal square root `V diag(sqrt(λ)) Vᵀ` of a symmetric
+    /// positive-definite matrix, or `None` if the matrix isn't SPD.
+    pub fn sqrt_spd(&self) -> Option<Matrix<T>> {
+        self.spd_function(|x| x.sqrt())
+    }
+
+    // Applies `f` to the eigenvalues of a symmetric positive-definite matrix,
+    // returning `V diag(f(λ)) Vᵀ`, or `None` if the matrix isn't SPD
+    fn spd_function(&self, f: fn(T) -> T) -> Option<Matrix<T>> {
+        let (values, vectors) = self.eig_symmetric()?;
+        let tolerance = self.suggested_tolerance();
+        if values.iter().any(|value| *value <= tolerance) {
+            return None;
+        }
+
+        let mut scaled = vectors.clone();
+        for (k, value) in values.into_iter().enumerate() {
+            let factor = f(value);
+            for i in 0..scaled.rows {
+                scaled.data[i * scaled.columns + k] *= factor;
+            }
+        }
+
+        Some(scaled * vectors.transpose())
+    }
+
+    /// Eigen-decomposition of a symmetric matrix by cyclic Jacobi rotations.
+    ///
+    /// Eigenvalues come back in ascending order, with the matching unit
+    /// eigenvectors as the columns of the returned matrix, so that
+    /// `A == V diag(λ) Vᵀ`. Returns `None` if the matrix isn't square and
+    /// symmetric, or if any entry isn't finite.
+    pub fn eig_symmetric(&self) -> Option<(Vec<T>, Matrix<T>)> {
+        if !self.is_symmetric() || self.data.iter().any(|value| !value.is_finite()) {
+            return None;
+        }
+        let n = self.rows;
+
+        // Iterate on A / max|a_ij| so the squared sums in the convergence test
+        // neither overflow nor underflow, and scale the eigenvalues back after
+        let scale = self
+            .data
+            .iter()
+            .fold(T::zero(), |max, value| max.max(value.abs()));
+        if scale.is_zero() {
+            return Some((vec![T::zero(); n], Matrix::identity(n)));
+        }
+        let mut a = self.scale(T::one() / scale);
+        let mut v = Matrix::identity(n);
+        let two = T::one() + T::one();
+
+        for _sweep in 0..100 {
+            let mut off_diagonal = T::zero();
+            let mut total = T::zero();
+            for i in 0..n {
+                for k in 0..n {
+                    let square = a.data[i * n + k] * a.data[i * n + k];
+                    total += square;
+                    if i != k {
+                        off_diagonal += square;
+                    }
+                }
+            }
+            if off_diagonal <= T::epsilon() * T::epsilon() * total {
+                break;
+            }
+
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let apq = a.data[p * n + q];
+                    if apq.is_zero() {
+                        continue;
+                    }
+                    let theta = (a.data[q * n + q] - a.data[p * n + p]) / (two * apq);
+                    let t = if theta >= T::zero() {
+                        T::one() / (theta + (theta * theta + T::one()).sqrt())
+                    } else {
+                        -T::one() / (-theta + (theta * theta + T::one()).sqrt())
+                    };
+                    let c = T::one() / (t * t + T::one()).sqrt();
+                    let s = t * c;
+
+                    for k in 0..n {
+                        let akp = a.data[k * n + p];
+                        let akq = a.data[k * n + q];
+                        a.data[k * n + p] = c * akp - s * akq;
+                        a.data[k * n + q] = s * akp + c * akq;
+                    }
+                    for k in 0..n {
+                        let apk = a.data[p * n + k];
+                        let aqk = a.data[q * n + k];
+                        a.data[p * n + k] = c * apk - s * aqk;
+                        a.data[q * n + k] = s * apk + c * aqk;
+                    }
+                    for k in 0..n {
+                        let vkp = v.data[k * n + p];
+                        let vkq = v.data[k * n + q];
+                        v.data[k * n + p] = c * vkp - s * vkq;
+                        v.data[k * n + q] = s * vkp + c * vkq;
+                    }
+                }
+            }
+        }
+
+        let mut order = (0..n).collect::<Vec<usize>>();
+        order.sort_by(|x, y| {
+            a.data[x * n + x]
+                .partial_cmp(&a.data[y * n + y])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let values = order.iter().map(|i| a.data[i * n + i] * scale).collect();
+        let mut vectors = Matrix::zeros(n, n);
+        for (k, i) in order.into_iter().enumerate() {
+            vectors.set_column(k, v.get_column(i));
+        }
+
+        Some((values, vectors))
+    }
+
+    /// Counts the `(positive, negative, zero)` eigenvalues of a symmetric
+    /// matrix without computing them. By Sylvester's law of inertia these
+    /// match the signs of the pivots of a symmetric LDLᵀ elimination; pivots
+    /// with magnitude at or below `tolerance` count as zero. Panics if the
+    /// matrix isn't symmetric.
+    pub fn inertia(&self, tolerance: T) -> (usize, usize, usize) {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can have an inertia.");
+        }
+        if !self.is_symmetric() {
+            panic!("Only symmetric matrixes have an inertia.");
+        }
+
+        let n = self.rows;
+        let mut a = self.clone();
+        let (mut positive, mut negative, mut zero) = (0, 0, 0);
+
+        for k in 0..n {
+            let mut pivot_index = k;
+            for i in (k + 1)..n {
+                if a.get(i, i).abs() > a.get(pivot_index, pivot_index).abs() {
+                    pivot_index = i;
+                }
+            }
+
+            if a.get(pivot_index, pivot_index).abs() <= tolerance {
+                // Every remaining diagonal entry vanishes, so look for an
+                // off-diagonal one and fold its row/column into another.
+                // This is a congruence, so it leaves the inertia unchanged
+                let mut largest = (k, k);
+                for i in k..n {
+                    for j in (i + 1)..n {
+                        if a.get(i, j).abs() > a.get(largest.0, largest.1).abs() {
+                            largest = (i, j);
+                        }
+                    }
+                }
+                let (i, j) = largest;
+                if a.get(i, j).abs() <= tolerance {
+                    zero += n - k;
+                    break;
+                }
+                for c in k..n {
+                    let value = *a.get(j, c);
+                    *a.get_mut(i, c) += value;
+                }
+                for r in k..n {
+                    let value = *a.get(r, j);
+                    *a.get_mut(r, i) += value;
+                }
+                pivot_index = i;
+            }
+
+            a.exchange_rows(pivot_index, k);
+            a.exchange_columns(pivot_index, k);
+
+            let pivot = *a.get(k, k);
+            if pivot > T::zero() {
+                positive += 1;
+            } else {
+                negative += 1;
+            }
+
+            for i in (k + 1)..n {
+                let m = *a.get(i, k) / pivot;
+                for j in (k + 1)..n {
+                    let value = *a.get(k, j);
+                    *a.get_mut(i, j) -= m * value;
+                }
+                a.set(i, k, T::zero());
+                a.set(k, i, T::zero());
+            }
+        }
+
+        (positive, negative, zero)
+    }
+
+    /// Factors a symmetric matrix as `L D Lᵀ`, returning the unit lower
+    /// triangular `L` and the diagonal of `D`. Unlike Cholesky this also
+    /// works for indefinite matrices, but since it doesn't pivot it returns
+    /// `None` whenever a zero pivot shows up, as well as for non-square or
+    /// non-symmetric input.
+    pub fn ldlt(&self) -> Option<(Matrix<T>, Vec<T>)> {
+        if !self.is_symmetric() {
+            return None;
+        }
+        let n = self.rows;
+        let tolerance = self.suggested_tolerance();
+
+        let mut lower = Matrix::identity(n);
+        let mut diagonal = Vec::with_capacity(n);
+        for j in 0..n {
+            let mut d = *self.get(j, j);
+            for (k, dk) in diagonal.iter().enumerate() {
+                d -= *lower.get(j, k) * *lower.get(j, k) * *dk;
+            }
+            if d.abs() <= tolerance {
+                return None;
+            }
+
+            for i in (j + 1)..n {
+                let mut value = *self.get(i, j);
+                for (k, dk) in diagonal.iter().enumerate() {
+                    value -= *lower.get(i, k) * *lower.get(j, k) * *dk;
+                }
+                lower.set(i, j, value / d);
+            }
+            diagonal.push(d);
+        }
+
+        Some((lower, diagonal))
+    }
+
+    /// Solves `A x = b` for a symmetric positive-definite `A` through its
+    /// Cholesky factor `L`, with a forward substitution on `L y = b` and a
+    /// back substitution on `Lᵀ x = y`. Returns `None` if `A` isn't SPD.
+    pub fn solve_cholesky(&self, b: &[T]) -> Option<Vec<T>> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be solved.");
+        }
+        if b.len() != self.rows {
+            panic!("Data is not the required size");
+        }
+
+        let lower = self.cholesky()?;
+        let n = self.rows;
+
+        let mut y = vec![T::zero(); n];
+        for i in 0..n {
+            let mut value = b[i];
+            for (k, yk) in y.iter().enumerate().take(i) {
+                value -= *lower.get(i, k) * *yk;
+            }
+            y[i] = value / *lower.get(i, i);
+        }
+
+        let mut x = vec![T::zero(); n];
+        for i in (0..n).rev() {
+            let mut value = y[i];
+            for (k, xk) in x.iter().enumerate().skip(i + 1) {
+                value -= *lower.get(k, i) * *xk;
+            }
+            x[i] = value / *lower.get(i, i);
+        }
+
+        Some(x)
+    }
+
+    /// Weighted least-squares solution of `A x ≈ b`, solving the normal
+    /// equations `Aᵀ W A x = Aᵀ W b` with `W = diag(weights)`. Returns `None`
+    /// when `Aᵀ W A` isn't positive-definite within the suggested tolerance,
+    /// e.g. for rank-deficient `A` or non-positive weights.
+    pub fn wlstsq(&self, b: &[T], weights: &[T]) -> Option<Vec<T>> {
+        if b.len() != self.rows || weights.len() != self.rows {
+            panic!("Data is not the required size");
+        }
+
+        let mut normal = Matrix::zeros(self.columns, self.columns);
+        let mut rhs = vec![T::zero(); self.columns];
+        for (i, (weight, value)) in weights.iter().zip(b.iter()).enumerate() {
+            let row = &self.data[i * self.columns..(i + 1) * self.columns];
+            for (j, a) in row.iter().enumerate() {
+                rhs[j] += *weight * *a * *value;
+                for (k, c) in row.iter().enumerate() {
+                    *normal.get_mut(j, k) += *weight * *a * *c;
+                }
+            }
+        }
+
+        normal.solve_cholesky(&rhs)
+    }
+
+    /// Ridge (Tikhonov) regression, solving `(AᵀA + lambda I) x = Aᵀ b`. Any
+    /// positive `lambda` makes the system positive-definite even for
+    /// rank-deficient `A`; `None` is only returned when it isn't.
+    pub fn ridge_solve(&self, b: &[T], lambda: T) -> Option<Vec<T>> {
+        if b.len() != self.rows {
+            panic!("Data is not the required size");
+        }
+
+        let transposed = self.transpose();
+        let mut normal = &transposed * self;
+        for i in 0..self.columns {
+            *normal.get_mut(i, i) += lambda;
+        }
+
+        normal.solve_cholesky(&transposed.mul_vec(b))
+    }
+
+    /// Cholesky factor: the lower triangular `L` with `L Lᵀ = A`. Returns
+    /// `None` when the matrix isn't symmetric positive-definite, i.e. when
+    /// it isn't symmetric or a diagonal term to take the root of is within
+    /// the suggested tolerance of zero or negative.
+    pub fn cholesky(&self) -> Option<Matrix<T>> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can have a Cholesky factor.");
+        }
+
+        let n = self.rows;
+        let tolerance = self.suggested_tolerance();
+        let mut lower = Matrix::zeros(n, n);
+
+        for j in 0..n {
+            for i in j..n {
+                if (*self.get(i, j) - *self.get(j, i)).abs() > tolerance {
+                    return None;
+                }
+
+                let mut value = *self.get(i, j);
+                for k in 0..j {
+                    value -= *lower.get(i, k) * *lower.get(j, k);
+                }
+
+                if i == j {
+                    if value <= tolerance {
+                        return None;
+                    }
+                    lower.set(j, j, value.sqrt());
+                } else {
+                    lower.set(i, j, value / *lower.get(j, j));
+                }
+            }
+        }
+
+        Some(lower)
+    }
+
+    /// Determinant of a symmetric positive-definite matrix as the squared
+    /// product of its Cholesky diagonal, which is cheaper and more stable
+    /// than elimination. Returns `None` when the matrix isn't SPD.
+    pub fn determinant_spd(&self) -> Option<T> {
+        if self.rows != self.columns {
+            return None;
+        }
+
+        let lower = self.cholesky()?;
+        let product = lower.get_diagonal().iter().copied().product::<T>();
+        Some(product * product)
+    }
+
+    /// Reduces the matrix to reduced row echelon form in place, by
+    /// Gauss-Jordan elimination with partial pivoting.
+    ///
+    /// Returns `false` if one of the leading `min(rows, columns)` columns
+    /// has no pivot, i.e. the square (coefficient) part of the matrix is
+    /// singular. For an `n x (n + 1)` augmented matrix `[A | b]` a `true`
+    /// result leaves the solution of `A x = b` in the last column.
+    pub fn gauss_jordan_mut(&mut self) -> bool {
+        let tolerance = self.suggested_tolerance();
+        let pivots = self.reduce_row_echelon(tolerance);
+
+        let leading = self.rows.min(self.columns);
+        pivots.len() >= leading && pivots.iter().take(leading).copied().eq(0..leading)
+    }
+
+    /// Reduced row echelon form, by Gauss-Jordan elimination with partial
+    /// pivoting. Works for any shape; entries within the suggested tolerance
+    /// of zero are flushed to exact zeros along the way.
+    pub fn rref(&self) -> Matrix<T> {
+        let mut reduced = self.clone();
+        reduced.reduce_row_echelon(self.suggested_tolerance());
+        reduced
+    }
+
+    /// Number of nonzero rows of the reduced row echelon form.
+    pub fn rank(&self) -> usize {
+        self.rank_within(self.suggested_tolerance())
+    }
+
+    /// Basis of the row space: the nonzero rows of the reduced row echelon
+    /// form. The number of vectors returned is the rank of the matrix.
+    pub fn row_space(&self) -> Vec<Vec<T>> {
+        let mut reduced = self.clone();
+        let pivots = reduced.reduce_row_echelon(self.suggested_tolerance());
+
+        (0..pivots.len()).map(|i| reduced.get_row(i)).collect()
+    }
+
+    /// Whether `b` lies in the span of the columns, i.e. whether `A x = b`
+    /// is consistent. Compares the rank of `A` with the rank of the
+    /// augmented `[A | b]`, treating values at or below `tolerance` as zero.
+    pub fn is_in_column_space(&self, b: &[T], tolerance: T) -> bool {
+        if b.len() != self.rows {
+            panic!("Data is not the required size");
+        }
+
+        let mut column = Matrix::zeros(self.rows, 1);
+        column.set_column(0, b.to_vec());
+        let augmented = self.hstack(&column);
+
+        self.rank_within(tolerance) == augmented.rank_within(tolerance)
+    }
+
+    /// Group inverse `A#`, the unique matrix with `A A# A = A`,
+    /// `A# A A# = A#` and `A A# = A# A`. It only exists for square matrices
+    /// of index one (rank(A) == rank(A²)); otherwise `None` is returned.
+    ///
+    /// Computed from the full-rank factorization `A = B C` taken from the
+    /// RREF, as `A# = B (C B)^-2 C`.
+    pub fn group_inverse(&self) -> Option<Matrix<T>> {
+        if self.rows != self.columns {
+            return None;
+        }
+
+        let mut reduced = self.clone();
+        let pivots = reduced.reduce_row_echelon(self.suggested_tolerance());
+        let rank = pivots.len();
+        if rank == 0 {
+            return Some(Matrix::zeros(self.rows, self.columns));
+        }
+
+        let b = self.columns_at(&pivots);
+        let mut c = Matrix::zeros(rank, self.columns);
+        for i in 0..rank {
+            c.set_row(i, reduced.get_row(i));
+        }
+
+        let core_inverse = (&c * &b).inverse()?;
+        Some(&(&(&b * &core_inverse) * &core_inverse) * &c)
+    }
+
+    /// Length of the part of `b` orthogonal to the column space, i.e. the
+    /// smallest residual `||A x - b||` any least-squares solution can reach.
+    /// Computed as `||b - Q Qᵀ b||` with `Q` an orthonormal basis of the
+    /// column space.
+    pub fn projection_residual(&self, b: &[T]) -> T {
+        if b.len() != self.rows {
+            panic!("Data is not the required size");
+        }
+
+        let projected = match self.column_space_basis() {
+            Some(q) => q.mul_vec(&q.transpose().mul_vec(b)),
+            None => vec![T::zero(); self.rows],
+        };
+
+        b.iter()
+            .zip(projected.iter())
+            .fold(T::zero(), |sum, (a, p)| sum + (*a - *p) * (*a - *p))
+            .sqrt()
+    }
+
+    // Orthonormal basis Q of the column space from the QR decomposition of
+    // the pivot columns, or None for a zero column space. Trailing pivots are
+    // dropped if they turn out dependent at the QR tolerance
+    fn column_space_basis(&self) -> Option<Matrix<T>> {
+        let mut pivots = self.clone().reduce_row_echelon(self.suggested_tolerance());
+        while !pivots.is_empty() {
+            if let Some((q, _)) = self.columns_at(&pivots).qr_decompose() {
+                return Some(q);
+            }
+            pivots.pop();
+        }
+        None
+    }
+
+    fn columns_at(&self, columns: &[usize]) -> Matrix<T> {
+        let mut matrix = Matrix::zeros(self.rows, columns.len());
+        for (k, column) in columns.iter().enumerate() {
+            matrix.set_column(k, self.get_column(*column));
+        }
+        matrix
+    }
+
+    /// Two-norm condition number `σ_max / σ_min` from the singular values,
+    /// or `None` when the matrix is rank-deficient (its smallest singular
+    /// value is numerically zero).
+    pub fn condition_number_svd(&self) -> Option<T> {
+        let values = self.singular_values()?;
+        let smallest = *values.first()?;
+        let largest = *values.last()?;
+        if smallest <= self.suggested_tolerance() {
+            return None;
+        }
+
+        Some(largest / smallest)
+    }
+
+    // Singular values in ascending order by one-sided Jacobi rotations on the
+    // columns of A (or Aᵀ when wide), without forming the Gram matrix, whose
+    // rounding would swamp small singular values. None if any entry isn't
+    // finite
+    fn singular_values(&self) -> Option<Vec<T>> {
+        if self.data.iter().any(|value| !value.is_finite()) {
+            return None;
+        }
+
+        let scale = self
+            .data
+            .iter()
+            .fold(T::zero(), |max, value| max.max(value.abs()));
+        let mut u = if self.rows >= self.columns {
+            self.clone()
+        } else {
+            self.transpose()
+        };
+        if scale.is_zero() {
+            return Some(vec![T::zero(); u.columns]);
+        }
+        u = u.scale(T::one() / scale);
+        let (m, n) = (u.rows, u.columns);
+        let two = T::one() + T::one();
+
+        for _sweep in 0..100 {
+            let mut rotated = false;
+            for p in 0..n {
+                for q in (p + 1)..n {
+                    let mut alpha = T::zero();
+                    let mut beta = T::zero();
+                    let mut gamma = T::zero();
+                    for i in 0..m {
+                        let (up, uq) = (u.data[i * n + p], u.data[i * n + q]);
+                        alpha += up * up;
+                        beta += uq * uq;
+                        gamma += up * uq;
+                    }
+                    if gamma.abs() <= T::epsilon() * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+
+                    let zeta = (beta - alpha) / (two * gamma);
+                    let t = if zeta >= T::zero() {
+                        T::one() / (zeta + (zeta * zeta + T::one()).sqrt())
+                    } else {
+                        -T::one() / (-zeta + (zeta * zeta + T::one()).sqrt())
+                    };
+                    let c = T::one() / (t * t + T::one()).sqrt();
+                    let s = t * c;
+                    for i in 0..m {
+                        let (up, uq) = (u.data[i * n + p], u.data[i * n + q]);
+                        u.data[i * n + p] = c * up - s * uq;
+                        u.data[i * n + q] = s * up + c * uq;
+                    }
+                }
+            }
+            if !rotated {
+                break;
+            }
+        }
+
+        let mut values = (0..n)
+            .map(|k| {
+                u.get_column(k)
+                    .iter()
+                    .fold(T::zero(), |sum, value| sum + *value * *value)
+                    .sqrt()
+                    * scale
+            })
+            .collect::<Vec<T>>();
+        values.sort_by(|x, y| x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal));
+        Some(values)
+    }
+
+    fn rank_within(&self, tolerance: T) -> usize {
+        self.clone().reduce_row_echelon(tolerance).len()
+    }
+
+    // Gauss-Jordan elimination shared by the echelon-form routines. Entries
+    // with magnitude at or below `tolerance` are treated (and stored) as
+    // exact zeros. Returns the indices of the pivot columns
+    fn reduce_row_echelon(&mut self, tolerance: T) -> Vec<usize> {
+        let mut pivots = Vec::new();
+        let mut row = 0;
+
+        for column in 0..self.columns {
+            if row >= self.rows {
+                break;
+            }
+
+            let mut pivot_row = row;
+            for x in (row + 1)..self.rows {
+                if self.get(x, column).abs() > self.get(pivot_row, column).abs() {
+                    pivot_row = x;
+                }
+            }
+            if self.get(pivot_row, column).abs() <= tolerance {
+                for x in row..self.rows {
+                    self.set(x, column, T::zero());
+                }
+                continue;
+            }
+            self.exchange_rows(pivot_row, row);
+
+            let pivot = *self.get(row, column);
+            for k in column..self.columns {
+                *self.get_mut(row, k) /= pivot;
+            }
+            for x in 0..self.rows {
+                if x == row {
+                    continue;
+                }
+                let m = *self.get(x, column);
+                if m.is_zero() {
+                    continue;
+                }
+                for k in column..self.columns {
+                    let value = *self.get(row, k);
+                    *self.get_mut(x, k) -= m * value;
+                }
+                self.set(x, column, T::zero());
+            }
+            self.set(row, column, T::one());
+
+            pivots.push(column);
+            row += 1;
+        }
+
+        pivots
+    }
+
+    /// Reverse Cuthill-McKee ordering of a symmetric matrix, read as a graph
+    /// with an edge wherever an off-diagonal entry exceeds `tolerance` in
+    /// magnitude. Applying it with `permute_symmetric` tends to shrink the
+    /// bandwidth.
+    pub fn rcm_ordering(&self, tolerance: T) -> Vec<usize> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes have a symmetric ordering.");
+        }
+
+        let neighbours = self.adjacency_lists(tolerance);
+        let mut visited = vec![false; self.rows];
+        let mut order = Vec::with_capacity(self.rows);
+
+        // Breadth-first search from a minimum degree vertex of each component,
+        // visiting the neighbours of every vertex in increasing degree
+        while order.len() < self.rows {
+            let start = (0..self.rows)
+                .filter(|i| !visited[*i])
+                .min_by_key(|i| neighbours[*i].len())
+                .unwrap();
+            visited[start] = true;
+            let mut head = order.len();
+            order.push(start);
+
+            while head < order.len() {
+                let mut next: Vec<usize> = neighbours[order[head]]
+                    .iter()
+                    .copied()
+                    .filter(|j| !visited[*j])
+                    .collect();
+                next.sort_by_key(|j| neighbours[*j].len());
+                for j in next {
+                    visited[j] = true;
+                    order.push(j);
+                }
+                head += 1;
+            }
+        }
+
+        order.reverse();
+        order
+    }
+
+    /// Normalized graph Laplacian `I - D^(-1/2) A D^(-1/2)` of a symmetric
+    /// adjacency matrix, as used in spectral clustering. Every vertex needs
+    /// a positive degree.
+    pub fn normalized_laplacian(&self) -> Matrix<T> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be adjacency matrixes.");
+        }
+
+        let scaling: Vec<T> = (0..self.rows)
+            .map(|i| {
+                let degree = self.data[i * self.columns..(i + 1) * self.columns]
+                    .iter()
+                    .fold(T::zero(), |sum, value| sum + *value);
+                if degree <= T::zero() {
+                    panic!("Every vertex needs a positive degree.");
+                }
+                degree.sqrt().recip()
+            })
+            .collect();
+
+        let mut laplacian = Matrix::identity(self.rows);
+        for (i, a) in scaling.iter().enumerate() {
+            for (j, b) in scaling.iter().enumerate() {
+                laplacian.data[i * self.columns + j] -= *a * *self.get(i, j) * *b;
+            }
+        }
+        laplacian
+    }
+
+    /// Number of connected components of the graph of a symmetric adjacency
+    /// matrix, with an edge wherever an off-diagonal entry exceeds
+    /// `tolerance` in magnitude. This is the multiplicity of the zero
+    /// eigenvalue of the graph Laplacian.
+    pub fn connected_components(&self, tolerance: T) -> usize {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be adjacency matrixes.");
+        }
+
+        let neighbours = self.adjacency_lists(tolerance);
+        let mut visited = vec![false; self.rows];
+        let mut components = 0;
+        for start in 0..self.rows {
+            if visited[start] {
+                continue;
+            }
+            components += 1;
+            visited[start] = true;
+            let mut stack = vec![start];
+            while let Some(i) = stack.pop() {
+                for j in neighbours[i].iter() {
+                    if !visited[*j] {
+                        visited[*j] = true;
+                        stack.push(*j);
+                    }
+                }
+            }
+        }
+        components
+    }
+
+    /// Binarized `A + A² + ... + A^steps` of an adjacency matrix: entry
+    /// `(i, j)` is one when `j` can be reached from `i` by a walk of at most
+    /// `steps` edges, and zero otherwise. Positive entries of `A` are edges.
+    pub fn reachability(&self, steps: usize) -> Matrix<T> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be adjacency matrixes.");
+        }
+
+        // Binarizing after every step keeps the entries from overflowing
+        let binarize = |m: &Matrix<T>| {
+            m.map(|value| {
+                if value > T::zero() {
+                    T::one()
+                } else {
+                    T::zero()
+                }
+            })
+        };
+        let edges = binarize(self);
+        let mut walks = Matrix::identity(self.rows);
+        let mut reachable = Matrix::zeros(self.rows, self.columns);
+        for _step in 0..steps {
+            walks = binarize(&(&walks * &edges));
+            reachable = binarize(&(&reachable + &walks));
+        }
+        reachable
+    }
+
+    /// Dominant eigenpair by power iteration from the normalized all-ones
+    /// vector. The eigenvalue is the Rayleigh quotient of the final unit
+    /// eigenvector. Iterates until the vector moves by less than
+    /// `tolerance` (up to sign, so negative dominant eigenvalues converge
+    /// too) or `iterations` steps are used up, whichever comes first.
+    /// Returns `None` for non-square matrices or when the iterate vanishes.
+    pub fn power_iteration(&self, iterations: usize, tolerance: T) -> Option<(T, Vec<T>)> {
+        if self.rows != self.columns || self.rows == 0 {
+            return None;
+        }
+
+        let norm = |v: &[T]| v.iter().fold(T::zero(), |sum, x| sum + *x * *x).sqrt();
+        let mut vector = vec![T::one() / T::from(self.rows).unwrap().sqrt(); self.rows];
+        for _iteration in 0..iterations {
+            let mut next = self.mul_vec(&vector);
+            let length = norm(&next);
+            if length.is_zero() {
+                return None;
+            }
+            for value in next.iter_mut() {
+                *value /= length;
+            }
+
+            let (same, flipped) = next.iter().zip(vector.iter()).fold(
+                (T::zero(), T::zero()),
+                |(same, flipped), (a, b)| {
+                    (
+                        same + (*a - *b) * (*a - *b),
+                        flipped + (*a + *b) * (*a + *b),
+                    )
+                },
+            );
+            vector = next;
+            if same.min(flipped).sqrt() < tolerance {
+                break;
+            }
+        }
+
+        let image = self.mul_vec(&vector);
+        let eigenvalue = image
+            .iter()
+            .zip(vector.iter())
+            .fold(T::zero(), |sum, (a, b)| sum + *a * *b);
+        Some((eigenvalue, vector))
+    }
+
+    /// Perron eigenvector of a non-negative irreducible matrix: the positive
+    /// dominant eigenvector, normalized to sum to one. Runs power iteration
+    /// on `A + I`, which has the same eigenvector but no other eigenvalue of
+    /// the same magnitude, so periodic matrices converge too. Returns `None`
+    /// for matrices with negative entries or when the iterates haven't moved
+    /// by less than `tolerance` (in the 1-norm) within `iterations` steps.
+    pub fn perron_vector(&self, iterations: usize, tolerance: T) -> Option<Vec<T>> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes have a Perron vector.");
+        }
+        if self.data.iter().any(|value| *value < T::zero()) {
+            return None;
+        }
+
+        let shifted = self + &Matrix::identity(self.rows);
+        let mut vector = vec![T::one() / T::from(self.rows).unwrap(); self.rows];
+        for _iteration in 0..iterations {
+            let mut next = shifted.mul_vec(&vector);
+            let sum = next.iter().fold(T::zero(), |sum, value| sum + *value);
+            if sum <= T::zero() {
+                return None;
+            }
+            for value in next.iter_mut() {
+                *value /= sum;
+            }
+
+            let change = next
+                .iter()
+                .zip(vector.iter())
+                .fold(T::zero(), |change, (a, b)| change + (*a - *b).abs());
+            vector = next;
+            if change <= tolerance {
+                return Some(vector);
+            }
+        }
+        None
+    }
+
+    /// PageRank of a column-stochastic transition matrix (`a_ij` is the
+    /// probability of following a link from page `j` to page `i`), by power
+    /// iteration on `r = damping * A r + (1 - damping) / n`. Probability
+    /// lost through pages without links is teleported uniformly as well, so
+    /// the ranks always sum to one. Returns `None` when the ranks haven't
+    /// moved by less than `tolerance` (in the 1-norm) within `iterations`
+    /// steps.
+    pub fn pagerank(&self, damping: T, iterations: usize, tolerance: T) -> Option<Vec<T>> {
+        if self.rows != self.columns {
+            panic!("Only nxn matrixes can be transition matrixes.");
+        }
+
+        let n = T::from(self.rows).unwrap();
+        let mut ranks = vec![T::one() / n; self.rows];
+        for _iteration in 0..iterations {
+            let mut next = self.mul_vec(&ranks);
+            for value in next.iter_mut() {
+                *value *= damping;
+            }
+            let kept = next.iter().fold(T::zero(), |sum, value| sum + *value);
+            let teleport = (T::one() - kept) / n;
+            for value in next.iter_mut() {
+                *value += teleport;
+            }
+
+            let change = next
+                .iter()
+                .zip(ranks.iter())
+                .fold(T::zero(), |change, (a, b)| change + (*a - *b).abs());
+            ranks = next;
+            if change <= tolerance {
+                return Some(ranks);
+            }
+        }
+        None
+    }
+
+    // Neighbours of every vertex when the matrix is read as a graph, with an
+    // edge i -> j wherever |a_ij| exceeds tolerance (self loops ignored)
+    fn adjacency_lists(&self, tolerance: T) -> Vec<Vec<usize>> {
+        (0..self.rows)
+            .map(|i| {
+                (0..self.columns)
+                    .filter(|j| *j != i && self.get(i, *j).abs() > tolerance)
+                    .collect()
+            })
+            .collect()
+    }
+
+    // (A + Aᵀ) / 2, used to wash out rounding asymmetry
+    fn symmetrized(&self) -> Matrix<T> {
+        let half = T::one() / (T::one() + T::one());
+        (&self.transpose() + self).scale(half)
+    }
+
+    /// Numerical zero threshold `max(rows, columns) * ε * max|a_ij|`, the
+    /// usual rank tolerance heuristic. Values at or below it are treated as
+    /// zero by the elimination routines and methods like `rank`.
+    pub fn suggested_tolerance(&self) -> T {
+        let max_abs = self
+            .data
+            .iter()
+            .fold(T::zero(), |max, value| max.max(value.abs()));
+        let max_dimension = T::from(self.rows.max(self.columns)).unwrap();
+        max_dimension * T::epsilon() * max_abs
+    }
+
+    fn frobenius_distance(&self, other: &Matrix<T>) -> T {
+        (self - other).frobenius_norm()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T: Num + NumAssign + Copy + Send + Sync> Matrix<T> {
+    /// Same product as `*`, with the rows of the result computed in
+    /// parallel on the rayon thread pool. Only worth it for large matrices,
+    /// as a rough benchmark:
+    ///
+    /// ```
+    /// use matrix::Matrix;
+    /// use std::time::Instant;
+    ///
+    /// let a = Matrix::new(256, 256, 0.5_f64);
+    ///
+    /// let start = Instant::now();
+    /// let sequential = &a * &a;
+    /// let sequential_time = start.elapsed();
+    ///
+    /// let start = Instant::now();
+    /// let parallel = a.par_mul(&a);
+    /// let parallel_time = start.elapsed();
+    ///
+    /// assert_eq!(parallel, sequential);
+    /// println!("sequential: {:?}, parallel: {:?}", sequential_time, parallel_time);
+    /// ```
+    pub fn par_mul(&self, other: &Matrix<T>) -> Matrix<T> {
+        use rayon::prelude::*;
+
+        if self.columns != other.rows {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.columns, other.columns),
+                    got: (other.rows, other.columns),
+                }
+            );
+        }
+
+        let mut data = vec![T::zero(); self.rows * other.columns];
+        if other.columns > 0 {
+            data.par_chunks_mut(other.columns)
+                .enumerate()
+                .for_each(|(i, output)| self.accumulate_row_product(other, i, output));
+        }
+
+        Matrix {
+            rows: self.rows,
+            columns: other.columns,
+            data,
+        }
+    }
+}
+
+impl<T: Num + NumAssign + Copy> Add for Matrix<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.checked_add(&other)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<T: Num + NumAssign + Copy> Sub for Matrix<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.checked_sub(&other)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<T: Num + NumAssign + Copy> Mul for Matrix<T> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.checked_mul(&other)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<T: Num + NumAssign + Copy> Add for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.checked_add(other)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<T: Num + NumAssign + Copy> Sub for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.checked_sub(other)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<T: Num + NumAssign + Copy> Mul for &Matrix<T> {
+    type Output = Matrix<T>;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.checked_mul(other)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+}
+
+impl<T: Num + NumAssign + Copy> AddAssign for Matrix<T> {
+    fn add_assign(&mut self, other: Self) {
+        if self.rows != other.rows || self.columns != other.columns {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.rows, self.columns),
+                    got: (other.rows, other.columns),
+                }
+            );
+        }
+
+        for (a, b) in self.data.iter_mut().zip(other.data.iter()) {
+            *a += *b;
+        }
+    }
+}
+
+impl<T: Num + NumAssign + Copy> SubAssign for Matrix<T> {
+    fn sub_assign(&mut self, other: Self) {
+        if self.rows != other.rows || self.columns != other.columns {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.rows, self.columns),
+                    got: (other.rows, other.columns),
+                }
+            );
+        }
+
+        for (a, b) in self.data.iter_mut().zip(other.data.iter()) {
+            *a -= *b;
+        }
+    }
+}
+
+impl<T: Num + NumAssign + Copy> MulAssign for Matrix<T> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = self
+            .checked_mul(&other)
+            .unwrap_or_else(|error| panic!("{}", error));
+    }
+}
+
+impl<T: Num + NumAssign + Copy + Neg<Output = T>> Neg for Matrix<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.map(|value| -value)
+    }
+}
+
+impl<T: Num + NumAssign + Copy> Mul<T> for Matrix<T> {
+    type Output = Self;
+
+    fn mul(self, factor: T) -> Self::Output {
+        self.scale(factor)
+    }
+}
+
+impl<T: Num + NumAssign + Copy> Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): (usize, usize)) -> &Self::Output {
+        self.get(row, column)
+    }
+}
+
+impl<T: Num + NumAssign + Copy> IndexMut<(usize, usize)> for Matrix<T> {
+    fn index_mut(&mut self, (row, column): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(row, column)
+    }
+}
+
+impl<T: Num + NumAssign + Copy + fmt::Display> fmt::Display for Matrix<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut display = String::new();
+        let mut index = 0;
+        for _i in 0..self.columns {
+            display += "{";
+            for _k in 0..self.rows {
+                display += &format!(" {},", self.data[index]);
+                index += 1;
+            }
+            display += " }\n";
+        }
+        write!(f, "{}", display)
+    }
+}
+
+// Whether the spread between the largest and smallest value is within tolerance
+fn is_constant<T: Float>(values: &[T], tolerance: T) -> bool {
+    let (low, high) = values
+        .iter()
+        .fold((T::infinity(), T::neg_infinity()), |(low, high), value| {
+            (low.min(*value), high.max(*value))
+        });
+    values.is_empty() || high - low <= tolerance
+}
+
+/// Element-wise running mean of a stream of equally shaped matrices,
+/// updated with Welford's method so the samples never need to be stored.
+#[derive(PartialEq, Debug, Clone)]
+pub struct RunningMeanMatrix<T: Float + NumAssign> {
+    count: usize,
+    mean: Matrix<T>,
+}
+
+impl<T: Float + NumAssign> RunningMeanMatrix<T> {
+    pub fn new(rows: usize, columns: usize) -> RunningMeanMatrix<T> {
+        RunningMeanMatrix {
+            count: 0,
+            mean: Matrix::zeros(rows, columns),
+        }
+    }
+
+    pub fn update(&mut self, m: &Matrix<T>) {
+        if self.mean.rows != m.rows || self.mean.columns != m.columns {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.mean.rows, self.mean.columns),
+                    got: (m.rows, m.columns),
+                }
+            );
+        }
+
+        self.count += 1;
+        let count = T::from(self.count).unwrap();
+        for (mean, value) in self.mean.data.iter_mut().zip(m.data.iter()) {
+            *mean += (*value - *mean) / count;
+        }
+    }
+
+    pub fn mean(&self) -> Matrix<T> {
+        self.mean.clone()
+    }
+}
+
+/// Exponentially weighted moving average of a stream of matrices. Each
+/// update computes `state = decay * state + (1 - decay) * m`, starting from
+/// a zero state as optimizers like Adam do.
+#[derive(PartialEq, Debug, Clone)]
+pub struct EwmaMatrix<T: Float + NumAssign> {
+    decay: T,
+    state: Matrix<T>,
+}
+
+impl<T: Float + NumAssign> EwmaMatrix<T> {
+    pub fn new(rows: usize, columns: usize, decay: T) -> EwmaMatrix<T> {
+        EwmaMatrix {
+            decay,
+            state: Matrix::zeros(rows, columns),
+        }
+    }
+
+    pub fn update(&mut self, m: &Matrix<T>) {
+        if self.state.rows != m.rows || self.state.columns != m.columns {
+            panic!(
+                "{}",
+                MatrixError::DimensionMismatch {
+                    expected: (self.state.rows, self.state.columns),
+                    got: (m.rows, m.columns),
+                }
+            );
+        }
+
+        let keep = T::one() - self.decay;
+        for (state, value) in self.state.data.iter_mut().zip(m.data.iter()) {
+            *state = self.decay * *state + keep * *value;
+        }
+    }
+
+    pub fn state(&self) -> Matrix<T> {
+        self.state.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)]
+    fn sum_two_matrix_1() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(4, 4, 3.0);
+        let result_matrix = Matrix::new(4, 4, 5.0);
+
+        matrix1 = matrix1 + matrix2;
+
+        assert_eq!(matrix1, result_matrix);
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)]
+    fn sum_two_matrix_2() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let mut matrix2 = Matrix::new(4, 4, 0.0);
+        let mut result_matrix = Matrix::new(4, 4, 2.0);
+
+        result_matrix.set(0, 0, 9.0);
+        matrix2.set(0, 0, 7.0);
+        matrix1 = matrix1 + matrix2;
+
+        assert_eq!(matrix1, result_matrix);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sum_two_matrix_3() {
+        let matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(4, 5, 0.0);
+
+        let _result = matrix1 + matrix2;
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)]
+    fn substract_two_matrix_1() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(4, 4, 3.0);
+        let result_matrix = Matrix::new(4, 4, -1.0);
+
+        matrix1 = matrix1 - matrix2;
+
+        assert_eq!(matrix1, result_matrix);
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)]
+    fn substract_two_matrix_2() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let mut matrix2 = Matrix::new(4, 4, 0.0);
+        let mut result_matrix = Matrix::new(4, 4, 2.0);
+
+        result_matrix.set(0, 0, -5.0);
+        matrix2.set(0, 0, 7.0);
+        matrix1 = matrix1 - matrix2;
+
+        assert_eq!(matrix1, result_matrix);
+    }
+
+    #[test]
+    #[should_panic]
+    fn substract_two_matrix_3() {
+        let matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(4, 5, 0.0);
+
+        let _result = matrix1 - matrix2;
+    }
+
+    #[test]
+    fn get_row_1() {
+        let mut matrix1 = Matrix::new(3, 4, 0.0);
+        matrix1.set(0, 0, 8.0);
+        matrix1.set(0, 1, 7.0);
+        matrix1.set(0, 2, 9.0);
+        matrix1.set(0, 3, 6.0);
+
+        let data = vec![8.0, 7.0, 9.0, 6.0];
+
+        assert_eq!(matrix1.get_row(0), data);
+    }
+
+    #[test]
+    fn get_row_2() {
+        let mut matrix1 = Matrix::new(3, 4, 0.0);
+
+        matrix1.set(2, 0, 8.0);
+        matrix1.set(2, 1, 7.0);
+        matrix1.set(2, 2, 9.0);
+        matrix1.set(2, 3, 6.0);
+
+        let data = vec![8.0, 7.0, 9.0, 6.0];
+
+        assert_eq!(matrix1.get_row(2), data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_row_3() {
+        let matrix1 = Matrix::new(3, 4, 0.0);
+
+        let _data = matrix1.get_row(4);
+    }
+
+    #[test]
+    fn get_column_1() {
+        let mut matrix1 = Matrix::new(4, 3, 0.0);
+        matrix1.set(0, 0, 8.0);
+        matrix1.set(1, 0, 7.0);
+        matrix1.set(2, 0, 9.0);
+        matrix1.set(3, 0, 6.0);
+
+        let data = vec![8.0, 7.0, 9.0, 6.0];
+
+        assert_eq!(matrix1.get_column(0), data);
+    }
+
+    #[test]
+    fn get_column_2() {
+        let mut matrix1 = Matrix::new(4, 3, 0.0);
+
+        matrix1.set(0, 2, 8.0);
+        matrix1.set(1, 2, 7.0);
+        matrix1.set(2, 2, 9.0);
+        matrix1.set(3, 2, 6.0);
+
+        let data = vec![8.0, 7.0, 9.0, 6.0];
+
+        assert_eq!(matrix1.get_column(2), data);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_column_3() {
+        let matrix1 = Matrix::new(4, 4, 0.0);
+
+        let _data = matrix1.get_column(4);
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)]
+    fn mult_matrix_1() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(4, 4, 2.0);
+        let result_matrix = Matrix::new(4, 4, 16.0);
+
+        matrix1 = matrix1 * matrix2;
+
+        assert_eq!(matrix1, result_matrix);
+    }
+
+    #[test]
+    #[allow(clippy::assign_op_pattern)]
+    fn mult_matrix_2() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let mut matrix2 = Matrix::new(4, 3, 4.0);
+        let mut result_matrix = Matrix::new(4, 3, 0.0);
+
+        matrix1.set(0, 0, 6.0);
+        matrix1.set(0, 1, 8.0);
+        matrix1.set(0, 2, 9.0);
+        matrix1.set(0, 3, 5.0);
+        matrix1.set(1, 0, 3.0);
+        matrix1.set(1, 1, 8.0);
+        matrix1.set(1, 2, 4.0);
+        matrix1.set(1, 3, 7.0);
+        matrix1.set(2, 0, 4.0);
+        matrix1.set(2, 1, 5.0);
+        matrix1.set(2, 2, 6.0);
+        matrix1.set(2, 3, 4.0);
+        matrix1.set(3, 0, 6.0);
+        matrix1.set(3, 1, 2.0);
+        matrix1.set(3, 2, 2.0);
+        matrix1.set(3, 3, 9.0);
+
+        matrix2.set(0, 0, 7.0);
+        matrix2.set(0, 1, 6.0);
+        matrix2.set(0, 2, 1.0);
+        matrix2.set(1, 0, 6.0);
+        matrix2.set(1, 1, 4.0);
+        matrix2.set(1, 2, 8.0);
+        matrix2.set(2, 0, 3.0);
+        matrix2.set(2, 1, 0.0);
+        matrix2.set(2, 2, 6.0);
+        matrix2.set(3, 0, 1.0);
+        matrix2.set(3, 1, 1.0);
+        matrix2.set(3, 2, 1.0);
+
+        result_matrix.set(0, 0, 122.0);
+        result_matrix.set(0, 1, 73.0);
+        result_matrix.set(0, 2, 129.0);
+        result_matrix.set(1, 0, 88.0);
+        result_matrix.set(1, 1, 57.0);
+        result_matrix.set(1, 2, 98.0);
+        result_matrix.set(2, 0, 80.0);
+        result_matrix.set(2, 1, 48.0);
+        result_matrix.set(2, 2, 84.0);
+        result_matrix.set(3, 0, 69.0);
+        result_matrix.set(3, 1, 53.0);
+        result_matrix.set(3, 2, 43.0);
+
+        matrix1 = matrix1 * matrix2;
+
+        assert_eq!(result_matrix, matrix1);
+    }
+
+    #[test]
+    fn exchange_rows_1() {
+        let mut matrix = Matrix::new(3, 4, 5.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(0, 1, 2.0);
+        matrix.set(0, 2, 3.0);
+        matrix.set(0, 3, 4.0);
+        matrix.set(1, 0, 5.0);
+        matrix.set(1, 1, 6.0);
+        matrix.set(1, 2, 7.0);
+        matrix.set(1, 3, 8.0);
+
+        matrix.exchange_rows(0, 1);
+
+        let row1 = vec![1.0, 2.0, 3.0, 4.0];
+        let row2 = vec![5.0, 6.0, 7.0, 8.0];
+
+        assert_eq!(row1, matrix.get_row(1));
+        assert_eq!(row2, matrix.get_row(0));
+    }
+
+    #[test]
+    fn exchange_rows_2() {
+        let mut matrix = Matrix::new(3, 4, 5.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(0, 1, 2.0);
+        matrix.set(0, 2, 3.0);
+        matrix.set(0, 3, 4.0);
+        matrix.set(2, 0, 5.0);
+        matrix.set(2, 1, 6.0);
+        matrix.set(2, 2, 7.0);
+        matrix.set(2, 3, 8.0);
+
+        matrix.exchange_rows(0, 2);
+
+        let row1 = vec![1.0, 2.0, 3.0, 4.0];
+        let row2 = vec![5.0, 6.0, 7.0, 8.0];
+
+        assert_eq!(row1, matrix.get_row(2));
+        assert_eq!(row2, matrix.get_row(0));
+    }
+
+    #[test]
+    fn exchange_columns_1() {
+        let mut matrix = Matrix::new(3, 4, 5.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(1, 0, 2.0);
+        matrix.set(2, 0, 3.0);
+        matrix.set(0, 1, 4.0);
+        matrix.set(1, 1, 5.0);
+        matrix.set(2, 1, 6.0);
+
+        matrix.exchange_columns(0, 1);
+
+        let column1 = vec![1.0, 2.0, 3.0];
+        let column2 = vec![4.0, 5.0, 6.0];
+
+        assert_eq!(column1, matrix.get_column(1));
+        assert_eq!(column2, matrix.get_column(0));
+    }
+
+    #[test]
+    fn exchange_columns_2() {
+        let mut matrix = Matrix::new(3, 4, 5.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(1, 0, 2.0);
+        matrix.set(2, 0, 3.0);
+        matrix.set(0, 3, 4.0);
+        matrix.set(1, 3, 5.0);
+        matrix.set(2, 3, 6.0);
+
+        matrix.exchange_columns(0, 3);
+
+        let column1 = vec![1.0, 2.0, 3.0];
+        let column2 = vec![4.0, 5.0, 6.0];
+
+        assert_eq!(column1, matrix.get_column(3));
+        assert_eq!(column2, matrix.get_column(0));
+    }
+
+    #[test]
+    fn set_row_1() {
+        let mut matrix = Matrix::new(3, 4, 5.0);
+        let vec1 = vec![1.0, 2.0, 3.0, 4.0];
+        matrix.set_row(0, vec1);
+
+        let vec2 = vec![1.0, 2.0, 3.0, 4.0];
+        let row = matrix.get_row(0);
+        assert_eq!(row, vec2);
+    }
+
+    #[test]
+    fn set_row_2() {
+        let mut matrix = Matrix::new(3, 4, 5.0);
+        let vec1 = vec![1.0, 2.0, 3.0, 4.0];
+        matrix.set_row(2, vec1);
+
+        let vec2 = vec![1.0, 2.0, 3.0, 4.0];
+        let row = matrix.get_row(2);
+        assert_eq!(row, vec2);
+    }
+
+    #[test]
+    fn set_column_1() {
+        let mut matrix = Matrix::new(3, 4, 5.0);
+        let vec1 = vec![1.0, 2.0, 3.0];
+        matrix.set_column(0, vec1);
+
+        let vec2 = vec![1.0, 2.0, 3.0];
+        let column = matrix.get_column(0);
+        assert_eq!(column, vec2);
+    }
+
+    #[test]
+    fn set_column_2() {
+        let mut matrix = Matrix::new(3, 4, 5.0);
+        let vec1 = vec![1.0, 2.0, 3.0];
+        matrix.set_column(3, vec1);
+
+        let vec2 = vec![1.0, 2.0, 3.0];
+        let column = matrix.get_column(3);
+        assert_eq!(column, vec2);
+    }
+
+    #[test]
+    fn get_diagonal_1() {
+        let mut matrix = Matrix::new(3, 3, 8.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(1, 1, 2.0);
+        matrix.set(2, 2, 3.0);
+
+        let vec1 = vec![1.0, 2.0, 3.0];
+        assert_eq!(matrix.get_diagonal(), vec1);
+    }
+
+    #[test]
+    fn get_diagonal_2() {
+        let mut matrix = Matrix::new(4, 4, 8.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(1, 1, 2.0);
+        matrix.set(2, 2, 3.0);
+        matrix.set(3, 3, 4.0);
+
+        let vec1 = vec![1.0, 2.0, 3.0, 4.0];
+        assert_eq!(matrix.get_diagonal(), vec1);
+    }
+
+    #[test]
+    fn get_determinant_1() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set(0, 0, 2.0);
+        matrix.set(1, 0, 4.0);
+        matrix.set(0, 1, 3.0);
+        matrix.set(1, 1, 5.0);
+
+        assert_eq!(matrix.get_determinant(), -2.0);
+    }
+
+    #[test]
+    fn get_determinant_2() {
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set(0, 0, 3.0);
+        matrix.set(1, 0, 4.0);
+        matrix.set(2, 0, 7.0);
+        matrix.set(0, 1, 2.0);
+        matrix.set(1, 1, 5.0);
+        matrix.set(2, 1, 8.0);
+        matrix.set(0, 2, 1.0);
+        matrix.set(1, 2, 6.0);
+        matrix.set(2, 2, 9.0);
+
+        assert_eq!(matrix.get_determinant(), 0.0);
+    }
+
+    #[test]
+    fn get_determinant_3() {
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set(0, 0, 0.0);
+        matrix.set(1, 0, 1.0);
+        matrix.set(2, 0, 2.0);
+        matrix.set(0, 1, 2.0);
+        matrix.set(1, 1, 3.0);
+        matrix.set(2, 1, 1.0);
+        matrix.set(0, 2, 1.0);
+        matrix.set(1, 2, 4.0);
+        matrix.set(2, 2, 3.0);
+
+        assert_eq!(matrix.get_determinant(), 5.0);
+    }
+
+    #[test]
+    fn get_determinant_4() {
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set(0, 0, 2.0);
+        matrix.set(1, 0, 0.0);
+        matrix.set(2, 0, 1.0);
+        matrix.set(0, 1, 3.0);
+        matrix.set(1, 1, 4.0);
+        matrix.set(2, 1, 0.0);
+        matrix.set(0, 2, 5.0);
+        matrix.set(1, 2, 1.0);
+        matrix.set(2, 2, 6.0);
+
+        assert_eq!(matrix.get_determinant(), 31.0);
+    }
+
+    #[test]
+    fn inverse_neumann_1() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(0, 1, 0.1);
+        matrix.set(1, 0, 0.2);
+        matrix.set(1, 1, 1.0);
+
+        // Exact inverse of [[1, 0.1], [0.2, 1]], determinant 0.98
+        let mut inverse = Matrix::new(2, 2, 0.0);
+        inverse.set(0, 0, 1.0 / 0.98);
+        inverse.set(0, 1, -0.1 / 0.98);
+        inverse.set(1, 0, -0.2 / 0.98);
+        inverse.set(1, 1, 1.0 / 0.98);
+
+        let approximation = matrix.inverse_neumann(40);
+        for i in 0..2 {
+            for k in 0..2 {
+                assert!((approximation.get(i, k) - inverse.get(i, k)).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_neumann_2() {
+        let matrix = Matrix::new(3, 3, 0.0);
+
+        assert_eq!(matrix.inverse_neumann(0), Matrix::new(3, 3, 0.0));
+    }
+
+    #[test]
+    fn scale_matrix_1() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(0, 1, 2.0);
+        matrix.set(1, 0, -3.0);
+        matrix.set(1, 1, 4.0);
+
+        let mut result_matrix = Matrix::new(2, 2, 0.0);
+        result_matrix.set(0, 0, 3.0);
+        result_matrix.set(0, 1, 6.0);
+        result_matrix.set(1, 0, -9.0);
+        result_matrix.set(1, 1, 12.0);
+
+        assert_eq!(matrix.scale(3.0), result_matrix);
+        assert_eq!(matrix * 3.0, result_matrix);
+    }
+
+    #[test]
+    fn scale_matrix_2() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(0, 1, 2.0);
+        matrix.set(1, 0, -3.0);
+        matrix.set(1, 1, 4.0);
+
+        let result_matrix = Matrix::new(2, 2, 0.0);
+
+        assert_eq!(matrix.scale(0.0), result_matrix);
+        assert_eq!(matrix * 0.0, result_matrix);
+    }
+
+    #[test]
+    fn transpose_1() {
+        let mut matrix = Matrix::new(2, 3, 0.0);
+        matrix.set_row(0, vec![1.0, 2.0, 3.0]);
+        matrix.set_row(1, vec![4.0, 5.0, 6.0]);
+
+        let transposed = matrix.transpose();
+
+        assert_eq!(transposed.get_row(0), vec![1.0, 4.0]);
+        assert_eq!(transposed.get_row(1), vec![2.0, 5.0]);
+        assert_eq!(transposed.get_row(2), vec![3.0, 6.0]);
+    }
+
+    #[test]
+    fn orthogonality_defect_1() {
+        let angle: f64 = 0.7;
+        let mut rotation = Matrix::new(2, 2, 0.0);
+        rotation.set(0, 0, angle.cos());
+        rotation.set(0, 1, -angle.sin());
+        rotation.set(1, 0, angle.sin());
+        rotation.set(1, 1, angle.cos());
+
+        assert!(rotation.orthogonality_defect() < 1e-12);
+    }
+
+    #[test]
+    fn orthogonality_defect_2() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set(0, 0, 0.3);
+        matrix.set(0, 1, 1.7);
+        matrix.set(1, 0, -2.1);
+        matrix.set(1, 1, 0.8);
+
+        assert!(matrix.orthogonality_defect() > 0.5);
+    }
+
+    #[test]
+    fn identity_1() {
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set_row(0, vec![2.0, -1.0, 0.5]);
+        matrix.set_row(1, vec![4.0, 3.0, 7.0]);
+        matrix.set_row(2, vec![-6.0, 8.0, 1.0]);
+        let mut result_matrix = Matrix::new(3, 3, 0.0);
+        result_matrix.set_row(0, vec![2.0, -1.0, 0.5]);
+        result_matrix.set_row(1, vec![4.0, 3.0, 7.0]);
+        result_matrix.set_row(2, vec![-6.0, 8.0, 1.0]);
+
+        assert_eq!(Matrix::identity(3) * matrix, result_matrix);
+    }
+
+    #[test]
+    fn identity_2() {
+        let matrix: Matrix<f64> = Matrix::identity(3);
+
+        assert_eq!(matrix.get_row(0), vec![1.0, 0.0, 0.0]);
+        assert_eq!(matrix.get_row(1), vec![0.0, 1.0, 0.0]);
+        assert_eq!(matrix.get_row(2), vec![0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn reorthogonalize_1() {
+        let angle: f64 = 0.4;
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set(0, 0, angle.cos() + 0.02);
+        matrix.set(0, 1, -angle.sin() - 0.01);
+        matrix.set(1, 0, angle.sin() + 0.015);
+        matrix.set(1, 1, angle.cos() - 0.03);
+
+        let defect = matrix.orthogonality_defect();
+        let corrected = matrix.reorthogonalize();
+
+        assert!(corrected.orthogonality_defect() < defect);
+        assert!(corrected.orthogonality_defect() < 1e-2);
+    }
+
+    #[test]
+    fn inverse_1() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set_row(0, vec![4.0, 7.0]);
+        matrix.set_row(1, vec![2.0, 6.0]);
+
+        let inverse = matrix.inverse().unwrap();
+        let product = matrix * inverse;
+        let identity: Matrix<f64> = Matrix::identity(2);
+
+        for i in 0..2 {
+            for k in 0..2 {
+                assert!((product.get(i, k) - identity.get(i, k)).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_2() {
+        // First pivot is zero, so a row exchange is required
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set_row(0, vec![0.0, 2.0, 1.0]);
+        matrix.set_row(1, vec![1.0, 3.0, 4.0]);
+        matrix.set_row(2, vec![2.0, 1.0, 3.0]);
+
+        let inverse = matrix.inverse().unwrap();
+        let product = matrix * inverse;
+        let identity: Matrix<f64> = Matrix::identity(3);
+
+        for i in 0..3 {
+            for k in 0..3 {
+                assert!((product.get(i, k) - identity.get(i, k)).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_3() {
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set_row(0, vec![1.0, 2.0, 3.0]);
+        matrix.set_row(1, vec![2.0, 4.0, 6.0]);
+        matrix.set_row(2, vec![7.0, 8.0, 9.0]);
+
+        assert_eq!(matrix.inverse(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn inverse_4() {
+        let matrix = Matrix::new(2, 3, 1.0);
+
+        let _inverse = matrix.inverse();
+    }
+
+    #[test]
+    fn spd_geometric_mean_1() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set_row(0, vec![4.0, 1.0]);
+        matrix.set_row(1, vec![1.0, 3.0]);
+
+        let mean = matrix.spd_geometric_mean(&matrix).unwrap();
+
+        for i in 0..2 {
+            for k in 0..2 {
+                assert!((mean.get(i, k) - matrix.get(i, k)).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn spd_geometric_mean_2() {
+        // For commuting diagonal matrices the mean is the element-wise
+        // geometric mean of the diagonals
+        let mut matrix1 = Matrix::new(2, 2, 0.0);
+        matrix1.set(0, 0, 4.0);
+        matrix1.set(1, 1, 1.0);
+        let mut matrix2 = Matrix::new(2, 2, 0.0);
+        matrix2.set(0, 0, 9.0);
+        matrix2.set(1, 1, 16.0);
+
+        let mean = matrix1.spd_geometric_mean(&matrix2).unwrap();
+
+        assert!((mean.get(0, 0) - 6.0).abs() < 1e-10);
+        assert!((mean.get(1, 1) - 4.0).abs() < 1e-10);
+        assert!(mean.get(0, 1).abs() < 1e-10);
+    }
+
+    #[test]
+    fn spd_geometric_mean_3() {
+        let mut matrix1 = Matrix::new(2, 2, 0.0);
+        matrix1.set_row(0, vec![4.0, 1.0]);
+        matrix1.set_row(1, vec![1.0, 3.0]);
+        let mut matrix2 = Matrix::new(2, 2, 0.0);
+        matrix2.set_row(0, vec![1.0, 2.0]);
+        matrix2.set_row(1, vec![2.0, 1.0]);
+
+        assert_eq!(matrix1.spd_geometric_mean(&matrix2), None);
+        assert_eq!(matrix2.spd_geometric_mean(&matrix1), None);
+    }
+
+    #[test]
+    fn checked_add_1() {
+        let matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(4, 4, 3.0);
+
+        assert_eq!(matrix1.checked_add(&matrix2), Ok(Matrix::new(4, 4, 5.0)));
+    }
+
+    #[test]
+    fn checked_add_2() {
+        // Same number of elements, different shape
+        let matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(2, 8, 0.0);
+
+        assert_eq!(
+            matrix1.checked_add(&matrix2),
+            Err(MatrixError::DimensionMismatch {
+                expected: (4, 4),
+                got: (2, 8)
+            })
+        );
+    }
+
+    #[test]
+    fn checked_sub_1() {
+        let matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(4, 5, 0.0);
+
+        assert_eq!(
+            matrix1.checked_sub(&matrix2),
+            Err(MatrixError::DimensionMismatch {
+                expected: (4, 4),
+                got: (4, 5)
+            })
+        );
+    }
+
+    #[test]
+    fn checked_mul_1() {
+        let matrix1 = Matrix::new(2, 3, 1.0);
+        let matrix2 = Matrix::new(2, 3, 1.0);
+
+        assert_eq!(
+            matrix1.checked_mul(&matrix2),
+            Err(MatrixError::DimensionMismatch {
+                expected: (3, 3),
+                got: (2, 3)
+            })
+        );
+    }
+
+    #[test]
+    fn checked_mul_2() {
+        let matrix1 = Matrix::new(2, 3, 1.0);
+        let matrix2 = Matrix::new(3, 2, 2.0);
+
+        assert_eq!(matrix1.checked_mul(&matrix2), Ok(Matrix::new(2, 2, 6.0)));
+    }
+
+    #[test]
+    fn sqrt_spd_1() {
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set_row(0, vec![4.0, 1.0, 0.5]);
+        matrix.set_row(1, vec![1.0, 3.0, 0.2]);
+        matrix.set_row(2, vec![0.5, 0.2, 2.0]);
+
+        let root = matrix.sqrt_spd().unwrap();
+        let square = root.checked_mul(&root).unwrap();
+
+        for i in 0..3 {
+            for k in 0..3 {
+                assert!((square.get(i, k) - matrix.get(i, k)).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt_spd_2() {
+        // Symmetric but indefinite
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set_row(0, vec![1.0, 2.0]);
+        matrix.set_row(1, vec![2.0, 1.0]);
+
+        assert_eq!(matrix.sqrt_spd(), None);
+    }
+
+    #[test]
+    fn index_1() {
+        let mut matrix = Matrix::new(2, 3, 0.0);
+        matrix.set(0, 1, 5.0);
+        matrix.set(1, 2, -2.0);
+
+        assert_eq!(matrix[(0, 1)], 5.0);
+        assert_eq!(matrix[(1, 2)], -2.0);
+        assert_eq!(matrix[(1, 0)], 0.0);
+    }
+
+    #[test]
+    fn index_2() {
+        let mut matrix = Matrix::new(2, 3, 0.0);
+        matrix[(0, 1)] = 5.0;
+        matrix[(1, 2)] += 3.0;
+
+        assert_eq!(*matrix.get(0, 1), 5.0);
+        assert_eq!(*matrix.get(1, 2), 3.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_3() {
+        let matrix = Matrix::new(2, 3, 0.0);
+
+        let _value = matrix[(2, 0)];
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_4() {
+        let mut matrix = Matrix::new(2, 3, 0.0);
+
+        matrix[(0, 3)] = 1.0;
+    }
+
+    #[test]
+    fn get_mut_1() {
+        let mut matrix = Matrix::new(3, 3, 1.0);
+        *matrix.get_mut(0, 0) += 1.0;
+        *matrix.get_mut(2, 1) = 7.0;
+
+        assert_eq!(*matrix.get(0, 0), 2.0);
+        assert_eq!(*matrix.get(2, 1), 7.0);
+        assert_eq!(*matrix.get(1, 1), 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_mut_2() {
+        let mut matrix = Matrix::new(3, 3, 1.0);
+
+        *matrix.get_mut(3, 0) = 2.0;
+    }
+
+    #[test]
+    fn eig_symmetric_1() {
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set_row(0, vec![2.0, -1.0, 0.0]);
+        matrix.set_row(1, vec![-1.0, 2.0, -1.0]);
+        matrix.set_row(2, vec![0.0, -1.0, 2.0]);
+
+        let (values, vectors) = matrix.eig_symmetric().unwrap();
+
+        // Known eigenvalues 2 - sqrt(2), 2, 2 + sqrt(2)
+        let sqrt2 = 2.0_f64.sqrt();
+        assert!((values[0] - (2.0 - sqrt2)).abs() < 1e-10);
+        assert!((values[1] - 2.0).abs() < 1e-10);
+        assert!((values[2] - (2.0 + sqrt2)).abs() < 1e-10);
+
+        let mut diagonal = Matrix::new(3, 3, 0.0);
+        for (i, value) in values.iter().enumerate() {
+            diagonal.set(i, i, *value);
+        }
+        let reconstructed = vectors
+            .checked_mul(&diagonal)
+            .unwrap()
+            .checked_mul(&vectors.transpose())
+            .unwrap();
+        for i in 0..3 {
+            for k in 0..3 {
+                assert!((reconstructed.get(i, k) - matrix.get(i, k)).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn eig_symmetric_2() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set_row(0, vec![1.0, 2.0]);
+        matrix.set_row(1, vec![3.0, 4.0]);
+
+        assert_eq!(matrix.eig_symmetric(), None);
+        assert_eq!(Matrix::new(2, 3, 1.0).eig_symmetric(), None);
+    }
+
+    #[test]
+    fn inertia_1() {
+        // Eigenvalues 3, -1 and 0
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set_row(0, vec![1.0, 2.0, 0.0]);
+        matrix.set_row(1, vec![2.0, 1.0, 0.0]);
+        matrix.set_row(2, vec![0.0, 0.0, 0.0]);
+
+        assert_eq!(matrix.inertia(1e-12), (1, 1, 1));
+    }
+
+    #[test]
+    fn inertia_2() {
+        // Zero diagonal, so the first pivot has to come from an
+        // off-diagonal entry. Eigenvalues are 1, -1, 2 and -2
+        let mut matrix = Matrix::new(4, 4, 0.0);
+        matrix.set_row(0, vec![0.0, 1.0, 0.0, 0.0]);
+        matrix.set_row(1, vec![1.0, 0.0, 0.0, 0.0]);
+        matrix.set_row(2, vec![0.0, 0.0, 0.0, 2.0]);
+        matrix.set_row(3, vec![0.0, 0.0, 2.0, 0.0]);
+
+        assert_eq!(matrix.inertia(1e-12), (2, 2, 0));
+    }
+
+    #[test]
+    fn inertia_3() {
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set_row(0, vec![4.0, 1.0, 0.5]);
+        matrix.set_row(1, vec![1.0, 3.0, 0.2]);
+        matrix.set_row(2, vec![0.5, 0.2, 2.0]);
+
+        assert_eq!(matrix.inertia(1e-12), (3, 0, 0));
+    }
+
+    #[test]
+    fn try_get_1() {
+        let mut matrix = Matrix::new(3, 4, 0.0);
+        matrix.set(2, 3, 6.0);
+
+        assert_eq!(matrix.try_get(2, 3), Some(&6.0));
+        assert_eq!(matrix.try_get(3, 0), None);
+        assert_eq!(matrix.try_get(0, 4), None);
+    }
+
+    #[test]
+    fn try_get_row_1() {
+        let mut matrix = Matrix::new(3, 4, 0.0);
+        matrix.set_row(1, vec![8.0, 7.0, 9.0, 6.0]);
+
+        assert_eq!(matrix.try_get_row(1), Some(vec![8.0, 7.0, 9.0, 6.0]));
+        assert_eq!(matrix.try_get_row(3), None);
+    }
+
+    #[test]
+    fn try_get_column_1() {
+        let mut matrix = Matrix::new(3, 4, 0.0);
+        matrix.set_column(3, vec![8.0, 7.0, 9.0]);
+
+        assert_eq!(matrix.try_get_column(3), Some(vec![8.0, 7.0, 9.0]));
+        assert_eq!(matrix.try_get_column(4), None);
+    }
+
+    #[test]
+    fn ldlt_1() {
+        // Symmetric indefinite, so Cholesky wouldn't apply
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set_row(0, vec![4.0, 2.0, -2.0]);
+        matrix.set_row(1, vec![2.0, -3.0, 1.0]);
+        matrix.set_row(2, vec![-2.0, 1.0, 5.0]);
+
+        let (lower, diagonal) = matrix.ldlt().unwrap();
+
+        let mut d = Matrix::new(3, 3, 0.0);
+        for (i, value) in diagonal.iter().enumerate() {
+            d.set(i, i, *value);
+        }
+        let reconstructed = lower
+            .checked_mul(&d)
+            .unwrap()
+            .checked_mul(&lower.transpose())
+            .unwrap();
+
+        for i in 0..3 {
+            assert_eq!(*lower.get(i, i), 1.0);
+            for k in (i + 1)..3 {
+                assert_eq!(*lower.get(i, k), 0.0);
+            }
+            for k in 0..3 {
+                assert!((reconstructed.get(i, k) - matrix.get(i, k)).abs() < 1e-12);
+            }
+        }
+        assert!(diagonal.iter().any(|value| *value < 0.0));
+    }
+
+    #[test]
+    fn ldlt_2() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set_row(0, vec![0.0, 1.0]);
+        matrix.set_row(1, vec![1.0, 0.0]);
+
+        assert_eq!(matrix.ldlt(), None);
+    }
+
+    #[test]
+    fn integer_matrix_1() {
+        let mut matrix1: Matrix<i32> = Matrix::new(2, 3, 0);
+        matrix1.set_row(0, vec![1, 2, 3]);
+        matrix1.set_row(1, vec![4, 5, 6]);
+        let mut matrix2: Matrix<i32> = Matrix::new(3, 2, 0);
+        matrix2.set_row(0, vec![7, 8]);
+        matrix2.set_row(1, vec![9, 10]);
+        matrix2.set_row(2, vec![11, 12]);
+
+        let mut result_matrix = Matrix::new(2, 2, 0);
+        result_matrix.set_row(0, vec![58, 64]);
+        result_matrix.set_row(1, vec![139, 154]);
+
+        assert_eq!(matrix1 * matrix2, result_matrix);
+    }
+
+    #[test]
+    fn integer_matrix_2() {
+        let mut matrix: Matrix<i64> = Matrix::identity(2);
+        matrix.set(0, 1, 3);
+
+        let sum = matrix.checked_add(&matrix.transpose()).unwrap();
+
+        assert_eq!(sum.get_row(0), vec![2, 3]);
+        assert_eq!(sum.get_row(1), vec![3, 2]);
+    }
+
+    #[test]
+    fn solve_cholesky_1() {
+        let mut matrix = Matrix::new(3, 3, 0.0);
+        matrix.set_row(0, vec![4.0, 12.0, -16.0]);
+        matrix.set_row(1, vec![12.0, 37.0, -43.0]);
+        matrix.set_row(2, vec![-16.0, -43.0, 98.0]);
+        let b = vec![1.0, 2.0, 3.0];
+
+        let x = matrix.solve_cholesky(&b).unwrap();
+
+        let mut column = Matrix::new(3, 1, 0.0);
+        column.set_column(0, b);
+        let expected = matrix.inverse().unwrap() * column;
+        for (i, value) in x.iter().enumerate() {
+            assert!((value - expected.get(i, 0)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn solve_cholesky_2() {
+        let mut matrix = Matrix::new(2, 2, 0.0);
+        matrix.set_row(0, vec![1.0, 2.0]);
+        matrix.set_row(1, vec![2.0, 1.0]);
+
+        assert_eq!(matrix.solve_cholesky(&[1.0, 1.0]), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn solve_cholesky_3() {
+        let matrix: Matrix<f64> = Matrix::identity(2);
+
+        let _x = matrix.solve_cholesky(&[1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn clone_1() {
+        let mut matrix = Matrix::new(2, 2, 1.0);
+        matrix.set(0, 1, 4.0);
+
+        let mut copy = matrix.clone();
+        copy.set(0, 1, 9.0);
+        copy.set(1, 0, -3.0);
+
+        assert_eq!(*matrix.get(0, 1), 4.0);
+        assert_eq!(*matrix.get(1, 0), 1.0);
+        assert_eq!(*copy.get(0, 1), 9.0);
+    }
+
+    #[test]
+    fn from_rows_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+        assert_eq!(matrix.get_row(0), vec![1.0, 2.0, 3.0]);
+        assert_eq!(matrix.get_column(2), vec![3.0, 6.0]);
+        assert_eq!(*matrix.get(1, 0), 4.0);
+        assert_eq!(*matrix.get(0, 1), 2.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_rows_2() {
+        let _matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0]]);
+    }
+
+    #[test]
+    fn from_2d_1() {
+        let matrix = Matrix::from_2d([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+
+        assert_eq!(
+            matrix,
+            Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]])
+        );
+        assert_eq!(*matrix.get(1, 2), 6.0);
+    }
+
+    #[test]
+    fn gauss_jordan_mut_1() {
+        // x + y + z = 6, 2y + 5z = -4, 2x + 5y - z = 27
+        let mut matrix = Matrix::from_rows(vec![
+            vec![1.0, 1.0, 1.0, 6.0],
+            vec![0.0, 2.0, 5.0, -4.0],
+            vec![2.0, 5.0, -1.0, 27.0],
+        ]);
+
+        assert!(matrix.gauss_jordan_mut());
+
+        let solution = [5.0, 3.0, -2.0];
+        for (i, value) in solution.iter().enumerate() {
+            for k in 0..3 {
+                let expected = if i == k { 1.0 } else { 0.0 };
+                assert!((matrix.get(i, k) - expected).abs() < 1e-12);
+            }
+            assert!((matrix.get(i, 3) - value).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn gauss_jordan_mut_2() {
+        let mut matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![2.0, 4.0, 7.0]]);
+
+        assert!(!matrix.gauss_jordan_mut());
+        assert_eq!(matrix.get_row(0), vec![1.0, 2.0, 0.0]);
+        assert_eq!(matrix.get_row(1), vec![0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn row_space_1() {
+        // Third row is the sum of the first two
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![0.0, 1.0, 1.0],
+            vec![1.0, 3.0, 4.0],
+        ]);
+
+        let basis = matrix.row_space();
+
+        assert_eq!(basis.len(), 2);
+        assert_eq!(basis[0], vec![1.0, 0.0, 1.0]);
+        assert_eq!(basis[1], vec![0.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn row_space_2() {
+        let matrix = Matrix::new(2, 3, 0.0);
+
+        assert!(matrix.row_space().is_empty());
+    }
+
+    #[test]
+    fn add_assign_1() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let mut matrix2 = Matrix::new(4, 4, 0.0);
+        let mut result_matrix = Matrix::new(4, 4, 2.0);
+
+        result_matrix.set(0, 0, 9.0);
+        matrix2.set(0, 0, 7.0);
+        matrix1 += matrix2;
+
+        assert_eq!(matrix1, result_matrix);
+    }
+
+    #[test]
+    #[should_panic]
+    fn add_assign_2() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(4, 5, 0.0);
+
+        matrix1 += matrix2;
+    }
+
+    #[test]
+    fn sub_assign_1() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let mut matrix2 = Matrix::new(4, 4, 0.0);
+        let mut result_matrix = Matrix::new(4, 4, 2.0);
+
+        result_matrix.set(0, 0, -5.0);
+        matrix2.set(0, 0, 7.0);
+        matrix1 -= matrix2;
+
+        assert_eq!(matrix1, result_matrix);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sub_assign_2() {
+        let mut matrix1 = Matrix::new(4, 4, 2.0);
+        let matrix2 = Matrix::new(4, 5, 0.0);
+
+        matrix1 -= matrix2;
+    }
+
+    #[test]
+    fn mul_assign_1() {
+        let mut matrix1 = Matrix::new(2, 4, 2.0);
+        let matrix2 = Matrix::new(4, 3, 2.0);
+
+        matrix1 *= matrix2;
+
+        assert_eq!(matrix1, Matrix::new(2, 3, 16.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_assign_2() {
+        let mut matrix1 = Matrix::new(2, 4, 2.0);
+        let matrix2 = Matrix::new(3, 3, 2.0);
+
+        matrix1 *= matrix2;
+    }
+
+    #[test]
+    fn is_in_column_space_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]);
+
+        assert!(matrix.is_in_column_space(&[2.0, 4.0, 6.0], 1e-12));
+        assert!(!matrix.is_in_column_space(&[1.0, 0.0, 0.0], 1e-12));
+    }
+
+    #[test]
+    fn is_in_column_space_2() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+        assert!(matrix.is_in_column_space(&[3.0, -7.0], 1e-12));
+    }
+
+    #[test]
+    fn borrowed_operators_1() {
+        let matrix1 = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let matrix2 = Matrix::from_rows(vec![vec![0.0, 1.0], vec![1.0, 0.0]]);
+        let matrix3 = Matrix::from_rows(vec![vec![2.0, 0.0], vec![0.0, 2.0]]);
+
+        let product1 = &matrix1 * &matrix2;
+        let product2 = &matrix1 * &matrix3;
+
+        assert_eq!(
+            product1,
+            Matrix::from_rows(vec![vec![2.0, 1.0], vec![4.0, 3.0]])
+        );
+        assert_eq!(
+            product2,
+            Matrix::from_rows(vec![vec![2.0, 4.0], vec![6.0, 8.0]])
+        );
+        assert_eq!(*matrix1.get(1, 1), 4.0);
+    }
+
+    #[test]
+    fn borrowed_operators_2() {
+        let matrix1 = Matrix::new(2, 2, 5.0);
+        let matrix2 = Matrix::new(2, 2, 3.0);
+
+        assert_eq!(&matrix1 + &matrix2, Matrix::new(2, 2, 8.0));
+        assert_eq!(&matrix1 - &matrix2, Matrix::new(2, 2, 2.0));
+        assert_eq!(matrix1, Matrix::new(2, 2, 5.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn borrowed_operators_3() {
+        let matrix1 = Matrix::new(2, 3, 5.0);
+        let matrix2 = Matrix::new(2, 3, 3.0);
+
+        let _product = &matrix1 * &matrix2;
+    }
+
+    #[test]
+    fn group_inverse_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![2.0, 1.0, 0.0],
+            vec![0.0, 1.0, 1.0],
+            vec![2.0, 2.0, 1.0],
+        ]);
+
+        let group = matrix.group_inverse().unwrap();
+
+        let identity1 = &(&matrix * &group) * &matrix;
+        let identity2 = &(&group * &matrix) * &group;
+        let commuted1 = &matrix * &group;
+        let commuted2 = &group * &matrix;
+        for i in 0..3 {
+            for k in 0..3 {
+                assert!((identity1.get(i, k) - matrix.get(i, k)).abs() < 1e-10);
+                assert!((identity2.get(i, k) - group.get(i, k)).abs() < 1e-10);
+                assert!((commuted1.get(i, k) - commuted2.get(i, k)).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn group_inverse_2() {
+        let matrix = Matrix::from_rows(vec![vec![2.0, 0.0], vec![0.0, 0.0]]);
+
+        assert_eq!(
+            matrix.group_inverse(),
+            Some(Matrix::from_rows(vec![vec![0.5, 0.0], vec![0.0, 0.0]]))
+        );
+    }
+
+    #[test]
+    fn group_inverse_3() {
+        // Nilpotent, index two
+        let matrix = Matrix::from_rows(vec![vec![0.0, 1.0], vec![0.0, 0.0]]);
+
+        assert_eq!(matrix.group_inverse(), None);
+    }
+
+    #[test]
+    fn map_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, -2.0], vec![3.0, 4.0]]);
+
+        let squared = matrix.map(|x| x * x);
+
+        assert_eq!(
+            squared,
+            Matrix::from_rows(vec![vec![1.0, 4.0], vec![9.0, 16.0]])
+        );
+        assert_eq!(*matrix.get(0, 1), -2.0);
+    }
+
+    #[test]
+    fn map_in_place_1() {
+        let mut matrix = Matrix::from_rows(vec![vec![1.5, -2.0, 0.0], vec![-0.5, 4.0, -3.0]]);
+
+        matrix.map_in_place(|x| if x < 0.0 { 0.0 } else { x });
+
+        assert_eq!(
+            matrix,
+            Matrix::from_rows(vec![vec![1.5, 0.0, 0.0], vec![0.0, 4.0, 0.0]])
+        );
+    }
+
+    #[test]
+    fn projection_residual_1() {
+        // Column space is the xy-plane, so only the z component remains
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0], vec![0.0, 1.0], vec![0.0, 0.0]]);
+
+        let residual = matrix.projection_residual(&[3.0, -1.0, 4.0]);
+
+        assert!((residual - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn projection_residual_2() {
+        // Rank-deficient: the column space is the line spanned by (1, 1)
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0], vec![1.0, 2.0]]);
+
+        let residual = matrix.projection_residual(&[1.0, -1.0]);
+        assert!((residual - 2.0_f64.sqrt()).abs() < 1e-12);
+
+        let residual = matrix.projection_residual(&[5.0, 5.0]);
+        assert!(residual.abs() < 1e-12);
+    }
+
+    #[test]
+    fn trace_1() {
+        let mut matrix = Matrix::new(3, 3, 8.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(1, 1, 2.0);
+        matrix.set(2, 2, 3.0);
+
+        assert_eq!(matrix.trace(), 6.0);
+    }
+
+    #[test]
+    fn trace_2() {
+        let mut matrix = Matrix::new(4, 4, 8.0);
+        matrix.set(0, 0, 1.0);
+        matrix.set(1, 1, -2.0);
+        matrix.set(2, 2, 3.5);
+        matrix.set(3, 3, 4.0);
+
+        assert_eq!(matrix.trace(), 6.5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn trace_3() {
+        let matrix = Matrix::new(3, 4, 1.0);
+
+        let _trace = matrix.trace();
+    }
+
+    #[test]
+    fn condition_number_svd_1() {
+        let angle: f64 = 0.3;
+        let rotation = Matrix::from_rows(vec![
+            vec![angle.cos(), -angle.sin()],
+            vec![angle.sin(), angle.cos()],
+        ]);
+        let diagonal = Matrix::from_rows(vec![vec![10.0, 0.0], vec![0.0, 2.0]]);
+
+        // Rotations don't change singular values, so the answer is 10 / 2
+        let matrix = &rotation * &diagonal;
+
+        assert!((matrix.condition_number_svd().unwrap() - 5.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn condition_number_svd_2() {
+        let matrix = Matrix::from_rows(vec![vec![3.0, 0.0, 0.0], vec![0.0, 1.0, 0.0]]);
+
+        assert!((matrix.condition_number_svd().unwrap() - 3.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn condition_number_svd_3() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+
+        assert_eq!(matrix.condition_number_svd(), None);
+    }
+
+    #[test]
+    fn approx_eq_1() {
+        let matrix1 = Matrix::from_rows(vec![vec![0.1 + 0.2, 1.0], vec![2.0, 3.0]]);
+        let matrix2 = Matrix::from_rows(vec![vec![0.3, 1.0], vec![2.0, 3.0 + 1e-12]]);
+
+        assert_ne!(matrix1, matrix2);
+        assert!(matrix1.approx_eq(&matrix2, 1e-9));
+        assert!(!matrix1.approx_eq(&matrix2, 1e-15));
+    }
+
+    #[test]
+    fn approx_eq_2() {
+        let matrix = Matrix::from_rows(vec![vec![4.0, 7.0], vec![2.0, 6.0]]);
+        let identity = Matrix::identity(2);
+
+        assert!((&matrix * &matrix.inverse().unwrap()).approx_eq(&identity, 1e-12));
+        assert!(!Matrix::new(2, 2, 0.0).approx_eq(&Matrix::new(2, 3, 0.0), 1.0));
+    }
+
+    #[test]
+    fn zeros_ones_1() {
+        let zeros: Matrix<f64> = Matrix::zeros(2, 3);
+        let ones: Matrix<f64> = Matrix::ones(3, 2);
+
+        assert_eq!(zeros, Matrix::new(2, 3, 0.0));
+        assert_eq!(ones, Matrix::new(3, 2, 1.0));
+    }
+
+    #[test]
+    fn from_diagonal_1() {
+        let matrix = Matrix::from_diagonal(vec![1.0, 2.0, 3.0]);
+
+        assert_eq!(matrix.get_row(0), vec![1.0, 0.0, 0.0]);
+        assert_eq!(matrix.get_row(1), vec![0.0, 2.0, 0.0]);
+        assert_eq!(matrix.get_row(2), vec![0.0, 0.0, 3.0]);
+        assert_eq!(matrix.get_diagonal(), vec![1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn relative_error_1() {
+        let matrix = Matrix::from_rows(vec![vec![101.0, 0.99], vec![-2.02, 0.0]]);
+        let reference = Matrix::from_rows(vec![vec![100.0, 1.0], vec![-2.0, 0.0]]);
+
+        let error = matrix.relative_error(&reference);
+
+        let expected = Matrix::from_rows(vec![vec![0.01, 0.01], vec![0.01, 0.0]]);
+        assert!(error.approx_eq(&expected, 1e-12));
+    }
+
+    #[test]
+    #[should_panic]
+    fn relative_error_2() {
+        let matrix = Matrix::new(2, 2, 1.0);
+
+        let _error = matrix.relative_error(&Matrix::new(2, 3, 1.0));
+    }
+
+    #[test]
+    fn from_diagonals_1() {
+        let matrix = Matrix::from_diagonals(
+            &[
+                (-1, vec![-1.0, -1.0, -1.0]),
+                (0, vec![2.0, 2.0, 2.0, 2.0]),
+                (1, vec![-3.0, -3.0, -3.0]),
+            ],
+            4,
+        );
+
+        assert_eq!(
+            matrix,
+            Matrix::from_rows(vec![
+                vec![2.0, -3.0, 0.0, 0.0],
+                vec![-1.0, 2.0, -3.0, 0.0],
+                vec![0.0, -1.0, 2.0, -3.0],
+                vec![0.0, 0.0, -1.0, 2.0],
+            ])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_diagonals_2() {
+        let _matrix = Matrix::from_diagonals(&[(1, vec![1.0, 1.0, 1.0])], 3);
+    }
+
+    #[test]
+    fn mul_vec_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![0.0, -1.0, 4.0],
+            vec![2.0, 0.0, 1.0],
+        ]);
+
+        assert_eq!(matrix.mul_vec(&[1.0, 2.0, 3.0]), vec![14.0, 10.0, 5.0]);
+    }
+
+    #[test]
+    fn mul_vec_2() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+        assert_eq!(matrix.mul_vec(&[1.0, 0.0, -1.0]), vec![-2.0, -2.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_vec_3() {
+        let matrix = Matrix::new(3, 3, 1.0);
+
+        let _result = matrix.mul_vec(&[1.0, 2.0]);
+    }
+
+    #[test]
+    fn dft_real_1() {
+        let (real, imaginary): (Matrix<f64>, Matrix<f64>) = Matrix::dft_real(4);
+
+        let expected_real = Matrix::from_rows(vec![
+            vec![1.0, 1.0, 1.0, 1.0],
+            vec![1.0, 0.0, -1.0, 0.0],
+            vec![1.0, -1.0, 1.0, -1.0],
+            vec![1.0, 0.0, -1.0, 0.0],
+        ]);
+        let expected_imaginary = Matrix::from_rows(vec![
+            vec![0.0, 0.0, 0.0, 0.0],
+            vec![0.0, -1.0, 0.0, 1.0],
+            vec![0.0, 0.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0, -1.0],
+        ]);
+
+        assert!(real.approx_eq(&expected_real, 1e-12));
+        assert!(imaginary.approx_eq(&expected_imaginary, 1e-12));
+    }
+
+    #[test]
+    fn dft_real_2() {
+        // A constant signal only has a DC component
+        let (real, imaginary): (Matrix<f64>, Matrix<f64>) = Matrix::dft_real(3);
+        let signal = [2.0, 2.0, 2.0];
+
+        let spectrum_real = real.mul_vec(&signal);
+        let spectrum_imaginary = imaginary.mul_vec(&signal);
+
+        assert!((spectrum_real[0] - 6.0).abs() < 1e-12);
+        assert!(spectrum_real[1..].iter().all(|value| value.abs() < 1e-12));
+        assert!(spectrum_imaginary.iter().all(|value| value.abs() < 1e-12));
+    }
+
+    #[test]
+    fn hadamard_1() {
+        let matrix1 = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let matrix2 = Matrix::from_rows(vec![vec![5.0, 0.0], vec![-1.0, 0.5]]);
+
+        assert_eq!(
+            matrix1.hadamard(&matrix2),
+            Matrix::from_rows(vec![vec![5.0, 0.0], vec![-3.0, 2.0]])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn hadamard_2() {
+        let matrix1 = Matrix::new(2, 3, 1.0);
+        let matrix2 = Matrix::new(3, 2, 1.0);
+
+        let _product = matrix1.hadamard(&matrix2);
+    }
+
+    #[test]
+    fn hadamard_matrix_1() {
+        let matrix: Matrix<f64> = Matrix::hadamard_matrix(4).unwrap();
+
+        assert_eq!(matrix.get_row(0), vec![1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(matrix.get_row(3), vec![1.0, -1.0, -1.0, 1.0]);
+        // Orthogonal rows of squared length 4
+        assert_eq!(
+            &matrix * &matrix.transpose(),
+            Matrix::identity(4).scale(4.0)
+        );
+    }
+
+    #[test]
+    fn hadamard_matrix_2() {
+        assert_eq!(
+            Matrix::<f64>::hadamard_matrix(1),
+            Some(Matrix::new(1, 1, 1.0))
+        );
+        assert_eq!(Matrix::<f64>::hadamard_matrix(0), None);
+        assert_eq!(Matrix::<f64>::hadamard_matrix(6), None);
+        assert!(Matrix::<i32>::hadamard_matrix(8).is_some());
+    }
+
+    #[test]
+    fn neg_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, -2.0, 0.0], vec![-3.5, 4.0, 7.0]]);
+
+        assert_eq!(
+            -matrix,
+            Matrix::from_rows(vec![vec![-1.0, 2.0, 0.0], vec![3.5, -4.0, -7.0]])
+        );
+    }
+
+    #[test]
+    fn neg_2() {
+        let matrix1 = Matrix::from_rows(vec![vec![5, -1], vec![2, 8]]);
+        let matrix2 = Matrix::from_rows(vec![vec![3, 4], vec![-6, 1]]);
+
+        assert_eq!(&matrix1 - &matrix2, matrix1 + -matrix2);
+    }
+
+    #[test]
+    fn symmetry_error_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![4.0, 1.0, 0.5],
+            vec![1.0, 3.0, 0.2],
+            vec![0.5, 0.2, 2.0],
+        ]);
+
+        assert!(matrix.symmetry_error() < 1e-15);
+    }
+
+    #[test]
+    fn symmetry_error_2() {
+        // Only (0, 1) and (1, 0) differ, by 3 each way
+        let matrix = Matrix::from_rows(vec![vec![1.0, 4.0], vec![1.0, 1.0]]);
+
+        assert!((matrix.symmetry_error() - 18.0_f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn frobenius_norm_1() {
+        let matrix = Matrix::from_rows(vec![vec![3.0, 4.0]]);
+
+        assert_eq!(matrix.frobenius_norm(), 5.0);
+    }
+
+    #[test]
+    fn frobenius_norm_2() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, -2.0], vec![2.0, -4.0]]);
+
+        assert_eq!(matrix.frobenius_norm(), 5.0);
+        assert_eq!(Matrix::new(3, 2, 0.0).frobenius_norm(), 0.0);
+    }
+
+    #[test]
+    fn clip_norm_1() {
+        let matrix = Matrix::from_rows(vec![vec![3.0, 4.0], vec![0.0, 0.0]]);
+
+        let clipped = matrix.clip_norm(1.0);
+
+        assert!((clipped.frobenius_norm() - 1.0).abs() < 1e-12);
+        assert!(clipped.approx_eq(&matrix.scale(0.2), 1e-12));
+    }
+
+    #[test]
+    fn clip_norm_2() {
+        let matrix = Matrix::from_rows(vec![vec![3.0, 4.0], vec![0.0, 0.0]]);
+
+        assert_eq!(matrix.clip_norm(10.0), matrix);
+        assert_eq!(matrix.clip_norm(5.0), matrix);
+    }
+
+    #[test]
+    fn vstack_1() {
+        let matrix1 = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let matrix2 = Matrix::from_rows(vec![vec![5.0, 6.0], vec![7.0, 8.0]]);
+
+        let stacked = matrix1.vstack(&matrix2);
+
+        assert_eq!((stacked.rows, stacked.columns), (4, 2));
+        assert_eq!(stacked.get_row(1), vec![3.0, 4.0]);
+        assert_eq!(stacked.get_row(2), vec![5.0, 6.0]);
+        assert_eq!(stacked.get_column(1), vec![2.0, 4.0, 6.0, 8.0]);
+    }
+
+    #[test]
+    fn hstack_1() {
+        let matrix1 = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let matrix2 = Matrix::from_rows(vec![vec![5.0, 6.0], vec![7.0, 8.0]]);
+
+        let stacked = matrix1.hstack(&matrix2);
+
+        assert_eq!((stacked.rows, stacked.columns), (2, 4));
+        assert_eq!(stacked.get_row(0), vec![1.0, 2.0, 5.0, 6.0]);
+        assert_eq!(stacked.get_row(1), vec![3.0, 4.0, 7.0, 8.0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn vstack_2() {
+        let _stacked = Matrix::new(2, 2, 1.0).vstack(&Matrix::new(2, 3, 1.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn hstack_2() {
+        let _stacked = Matrix::new(2, 2, 1.0).hstack(&Matrix::new(3, 2, 1.0));
+    }
+
+    #[test]
+    fn running_mean_1() {
+        let samples = [
+            Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]),
+            Matrix::from_rows(vec![vec![-2.0, 0.5], vec![7.0, 1.0]]),
+            Matrix::from_rows(vec![vec![4.0, -3.0], vec![0.0, 2.5]]),
+            Matrix::from_rows(vec![vec![0.5, 1.5], vec![-1.0, 8.0]]),
+        ];
+        let mut running = RunningMeanMatrix::new(2, 2);
+        for sample in samples.iter() {
+            running.update(sample);
+        }
+
+        let mut batch = Matrix::zeros(2, 2);
+        for sample in samples.iter() {
+            batch += sample.clone();
+        }
+        let batch = batch * 0.25;
+
+        assert!(running.mean().approx_eq(&batch, 1e-12));
+    }
+
+    #[test]
+    #[should_panic]
+    fn running_mean_2() {
+        let mut running = RunningMeanMatrix::new(2, 2);
+        running.update(&Matrix::new(3, 2, 1.0));
+    }
+
+    #[test]
+    fn submatrix_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec![5.0, 6.0, 7.0, 8.0],
+            vec![9.0, 10.0, 11.0, 12.0],
+            vec![13.0, 14.0, 15.0, 16.0],
+        ]);
+
+        let block = matrix.submatrix(1, 3, 1, 3);
+
+        assert_eq!(
+            block,
+            Matrix::from_rows(vec![vec![6.0, 7.0], vec![10.0, 11.0]])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn submatrix_2() {
+        let _block = Matrix::new(4, 4, 1.0).submatrix(1, 5, 0, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn submatrix_3() {
+        let _block = Matrix::new(4, 4, 1.0).submatrix(0, 2, 3, 1);
+    }
+
+    #[test]
+    fn ewma_1() {
+        let input = Matrix::from_rows(vec![vec![1.0, -2.0], vec![0.5, 3.0]]);
+        let mut ewma = EwmaMatrix::new(2, 2, 0.9);
+
+        ewma.update(&input);
+        assert!(ewma.state().approx_eq(
+            &Matrix::from_rows(vec![vec![0.1, -0.2], vec![0.05, 0.3]]),
+            1e-12
+        ));
+
+        for _i in 0..500 {
+            ewma.update(&input);
+        }
+        assert!(ewma.state().approx_eq(&input, 1e-12));
+    }
+
+    #[test]
+    #[should_panic]
+    fn ewma_2() {
+        let mut ewma = EwmaMatrix::new(2, 2, 0.9);
+        ewma.update(&Matrix::new(2, 3, 1.0));
+    }
+
+    #[test]
+    fn without_row_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ]);
+
+        assert_eq!(
+            matrix.without_row(1),
+            Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![7.0, 8.0, 9.0]])
+        );
+    }
+
+    #[test]
+    fn without_column_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ]);
+
+        assert_eq!(
+            matrix.without_column(1),
+            Matrix::from_rows(vec![vec![1.0, 3.0], vec![4.0, 6.0], vec![7.0, 9.0]])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn without_row_2() {
+        let _matrix = Matrix::new(3, 3, 1.0).without_row(3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn without_column_2() {
+        let _matrix = Matrix::new(3, 3, 1.0).without_column(3);
+    }
+
+    #[test]
+    fn accumulate_outer_1() {
+        let vectors = [
+            vec![1.0, 2.0, 3.0],
+            vec![-1.0, 0.5, 2.0],
+            vec![4.0, 0.0, -2.0],
+        ];
+        let mut scatter = Matrix::zeros(3, 3);
+        for v in vectors.iter() {
+            scatter.accumulate_outer(v);
+        }
+
+        let mut expected = Matrix::zeros(3, 3);
+        for v in vectors.iter() {
+            let column = Matrix::from_rows(v.iter().map(|x| vec![*x]).collect());
+            expected += &column * &column.transpose();
+        }
+
+        assert_eq!(scatter, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn accumulate_outer_2() {
+        let mut scatter = Matrix::zeros(3, 3);
+        scatter.accumulate_outer(&[1.0, 2.0]);
+    }
+
+    #[test]
+    fn shape_1() {
+        let matrix = Matrix::new(3, 4, 0.0);
+
+        assert_eq!(matrix.rows(), 3);
+        assert_eq!(matrix.columns(), 4);
+        assert_eq!(matrix.shape(), (3, 4));
+    }
+
+    #[test]
+    fn wlstsq_1() {
+        // Fit y = c0 + c1 x through points that don't lie on one line
+        let design = Matrix::from_rows(vec![
+            vec![1.0, 0.0],
+            vec![1.0, 1.0],
+            vec![1.0, 2.0],
+            vec![1.0, 3.0],
+        ]);
+        let observed = [1.0, 2.0, 2.0, 6.0];
+
+        let uniform = design.wlstsq(&observed, &[1.0, 1.0, 1.0, 1.0]).unwrap();
+        let weighted = design.wlstsq(&observed, &[1.0, 1.0, 1.0, 1000.0]).unwrap();
+
+        let uniform_miss = (uniform[0] + 3.0 * uniform[1] - 6.0).abs();
+        let weighted_miss = (weighted[0] + 3.0 * weighted[1] - 6.0).abs();
+        assert!(weighted_miss < 0.01);
+        assert!(weighted_miss < uniform_miss);
+    }
+
+    #[test]
+    fn wlstsq_2() {
+        // Equal weights give the ordinary least-squares line y = 0.5 + 1.5 x
+        let design = Matrix::from_rows(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]);
+
+        let solution = design.wlstsq(&[0.0, 3.0, 3.0], &[2.0, 2.0, 2.0]).unwrap();
+
+        assert!((solution[0] - 0.5).abs() < 1e-12);
+        assert!((solution[1] - 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn wlstsq_3() {
+        let design = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]);
+        let equal_columns = Matrix::from_rows(vec![vec![1.0, 1.0], vec![1.0, 1.0]]);
+
+        assert_eq!(design.wlstsq(&[1.0, 2.0, 3.0], &[1.0, 1.0, 1.0]), None);
+        assert_eq!(equal_columns.wlstsq(&[1.0, 2.0], &[1.0, 1.0]), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+        let json = serde_json::to_string(&matrix).unwrap();
+        let restored: Matrix<f64> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(restored, matrix);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_2() {
+        let json = r#"{"rows":2,"columns":3,"data":[1.0,2.0,3.0,4.0,5.0]}"#;
+
+        assert!(serde_json::from_str::<Matrix<f64>>(json).is_err());
+    }
+
+    #[test]
+    fn ridge_solve_1() {
+        let design = Matrix::from_rows(vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]]);
+        let observed = [0.0, 3.0, 3.0];
+
+        let unregularized = design.ridge_solve(&observed, 0.0).unwrap();
+        let small = design.ridge_solve(&observed, 0.1).unwrap();
+        let large = design.ridge_solve(&observed, 1e6).unwrap();
+
+        assert!((unregularized[0] - 0.5).abs() < 1e-12);
+        assert!((unregularized[1] - 1.5).abs() < 1e-12);
+        let norm = |x: &Vec<f64>| x.iter().map(|v| v * v).sum::<f64>().sqrt();
+        assert!(norm(&small) < norm(&unregularized));
+        assert!(norm(&large) < 1e-4);
+    }
+
+    #[test]
+    fn ridge_solve_2() {
+        // AᵀA is singular here, the regularization still gives a solution
+        let design = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]);
+
+        let equal_columns = Matrix::from_rows(vec![vec![1.0, 1.0], vec![1.0, 1.0]]);
+
+        assert_eq!(design.ridge_solve(&[1.0, 2.0, 3.0], 0.0), None);
+        assert!(design.ridge_solve(&[1.0, 2.0, 3.0], 0.5).is_some());
+        assert_eq!(equal_columns.ridge_solve(&[1.0, 2.0], 0.0), None);
+        assert!(equal_columns.ridge_solve(&[1.0, 2.0], 0.5).is_some());
+    }
+
+    #[test]
+    fn lu_decompose_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 10.0],
+        ]);
+
+        let (lower, upper, permutation) = matrix.lu_decompose().unwrap();
+
+        let mut permuted = Matrix::zeros(3, 3);
+        for (i, row) in permutation.iter().enumerate() {
+            permuted.set_row(i, matrix.get_row(*row));
+        }
+        assert!((&lower * &upper).approx_eq(&permuted, 1e-12));
+        for i in 0..3 {
+            assert_eq!(*lower.get(i, i), 1.0);
+            for k in (i + 1)..3 {
+                assert_eq!(*lower.get(i, k), 0.0);
+                assert_eq!(*upper.get(k, i), 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn lu_decompose_2() {
+        let singular = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+        let rectangular = Matrix::new(2, 3, 1.0);
+
+        assert_eq!(singular.lu_decompose(), None);
+        assert_eq!(rectangular.lu_decompose(), None);
+    }
+
+    #[test]
+    fn constant_columns_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 5.0, 2.0],
+            vec![2.0, 5.0, 2.0 + 1e-12],
+            vec![3.0, 5.0, 2.5],
+        ]);
+
+        assert_eq!(matrix.constant_columns(1e-9), vec![1]);
+        assert_eq!(matrix.constant_columns(1.0), vec![1, 2]);
+    }
+
+    #[test]
+    fn constant_rows_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 4.0, 4.0],
+            vec![0.0, 1e-12, 0.0],
+        ]);
+
+        assert_eq!(matrix.constant_rows(1e-9), vec![1, 2]);
+    }
+
+    #[test]
+    fn solve_1() {
+        // 2x + y = 5, x - 3y = -1 has the solution x = 2, y = 1
+        let matrix = Matrix::from_rows(vec![vec![2.0, 1.0], vec![1.0, -3.0]]);
+
+        let solution = matrix.solve(&[5.0, -1.0]).unwrap();
+
+        assert!((solution[0] - 2.0).abs() < 1e-12);
+        assert!((solution[1] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn solve_2() {
+        // Needs a row exchange on the first pivot
+        let matrix = Matrix::from_rows(vec![
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+            vec![3.0, 0.0, 1.0],
+        ]);
+        let expected = [1.0, -2.0, 3.0];
+
+        let b = matrix.mul_vec(&expected);
+        let solution = matrix.solve(&b).unwrap();
+
+        for (x, e) in solution.iter().zip(expected.iter()) {
+            assert!((x - e).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn solve_3() {
+        let singular = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0]]);
+
+        assert_eq!(singular.solve(&[1.0, 2.0]), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn solve_4() {
+        let matrix: Matrix<f64> = Matrix::identity(2);
+        let _x = matrix.solve(&[1.0, 2.0, 3.0]);
+    }
+
+    #[test]
+    fn select_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ]);
+
+        assert_eq!(
+            matrix.select(&[0, 2], &[1]),
+            Matrix::from_rows(vec![vec![2.0], vec![8.0]])
+        );
+        assert_eq!(
+            matrix.select(&[2, 0], &[2, 0]),
+            Matrix::from_rows(vec![vec![9.0, 7.0], vec![3.0, 1.0]])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn select_2() {
+        let _matrix = Matrix::new(3, 3, 1.0).select(&[0], &[3]);
+    }
+
+    #[test]
+    fn rref_1() {
+        // Augmented matrix of x + 2y - z = 2, 2x + y + z = 7, 3x + 3y = 9
+        let augmented = Matrix::from_rows(vec![
+            vec![1.0, 2.0, -1.0, 2.0],
+            vec![2.0, 1.0, 1.0, 7.0],
+            vec![3.0, 3.0, 0.0, 9.0],
+        ]);
+
+        let reduced = augmented.rref();
+
+        // The third equation is the sum of the other two, so the last row
+        // vanishes and z is free
+        let expected = Matrix::from_rows(vec![
+            vec![1.0, 0.0, 1.0, 4.0],
+            vec![0.0, 1.0, -1.0, -1.0],
+            vec![0.0, 0.0, 0.0, 0.0],
+        ]);
+        assert!(reduced.approx_eq(&expected, 1e-12));
+        assert_eq!(reduced.get_row(2), vec![0.0; 4]);
+    }
+
+    #[test]
+    fn rref_2() {
+        let matrix = Matrix::from_rows(vec![vec![0.0, 2.0], vec![3.0, 0.0], vec![1.0, 1.0]]);
+
+        let reduced = matrix.rref();
+
+        assert!(reduced.approx_eq(
+            &Matrix::from_rows(vec![vec![1.0, 0.0], vec![0.0, 1.0], vec![0.0, 0.0]]),
+            1e-12
+        ));
+    }
+
+    #[test]
+    fn permute_symmetric_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![4.0, 1.0, 2.0],
+            vec![1.0, 5.0, 3.0],
+            vec![2.0, 3.0, 6.0],
+        ]);
+
+        let permuted = matrix.permute_symmetric(&[2, 0, 1]);
+
+        assert_eq!(permuted, permuted.transpose());
+        assert_eq!(permuted.get_diagonal(), vec![6.0, 4.0, 5.0]);
+        assert_eq!(*permuted.get(0, 1), 2.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn permute_symmetric_2() {
+        let matrix: Matrix<f64> = Matrix::identity(3);
+        let _permuted = matrix.permute_symmetric(&[0, 0, 1]);
+    }
+
+    #[test]
+    fn rank_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![2.0, 1.0, 0.0],
+            vec![1.0, 3.0, 1.0],
+            vec![0.0, 1.0, 4.0],
+        ]);
+
+        assert_eq!(matrix.rank(), 3);
+    }
+
+    #[test]
+    fn rank_2() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![1.0, 2.0, 3.0],
+        ]);
+        let wide = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0, 4.0], vec![2.0, 4.0, 6.0, 8.0]]);
+
+        assert_eq!(matrix.rank(), 2);
+        assert_eq!(wide.rank(), 1);
+        assert_eq!(Matrix::new(2, 3, 0.0).rank(), 0);
+    }
+
+    #[test]
+    fn rcm_ordering_1() {
+        // A path graph 0 - 4 - 1 - 3 - 2 - 5 with its vertices scrambled
+        let edges = [(0, 4), (4, 1), (1, 3), (3, 2), (2, 5)];
+        let mut matrix = Matrix::identity(6);
+        for (i, j) in edges.iter() {
+            matrix.set(*i, *j, 1.0);
+            matrix.set(*j, *i, 1.0);
+        }
+        let bandwidth = |m: &Matrix<f64>| {
+            let mut width = 0;
+            for i in 0..6 {
+                for j in 0..6 {
+                    if *m.get(i, j) != 0.0 {
+                        width = width.max(i.abs_diff(j));
+                    }
+                }
+            }
+            width
+        };
+
+        let order = matrix.rcm_ordering(0.0);
+        let reordered = matrix.permute_symmetric(&order);
+
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..6).collect::<Vec<usize>>());
+        assert!(bandwidth(&reordered) <= bandwidth(&matrix));
+        assert_eq!(bandwidth(&reordered), 1);
+        assert_eq!(reordered, reordered.transpose());
+    }
+
+    #[test]
+    fn rcm_ordering_2() {
+        // Two disconnected components are both ordered
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 0.0, 1.0, 0.0],
+            vec![0.0, 1.0, 0.0, 1.0],
+            vec![1.0, 0.0, 1.0, 0.0],
+            vec![0.0, 1.0, 0.0, 1.0],
+        ]);
+
+        let order = matrix.rcm_ordering(0.0);
+
+        let mut sorted = order.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![0, 1, 2, 3]);
+        let reordered = matrix.permute_symmetric(&order);
+        assert_eq!(*reordered.get(0, 2), 0.0);
+        assert_eq!(*reordered.get(0, 3), 0.0);
+    }
+
+    #[test]
+    fn pow_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+
+        assert_eq!(matrix.pow(0), Matrix::identity(2));
+        assert_eq!(matrix.pow(1), matrix);
+        assert_eq!(matrix.pow(2), &matrix * &matrix);
+        assert_eq!(matrix.pow(5), &(&matrix * &matrix.pow(2)) * &matrix.pow(2));
+    }
+
+    #[test]
+    fn pow_2() {
+        // Powers of the Fibonacci matrix hold consecutive Fibonacci numbers
+        let fibonacci = Matrix::from_rows(vec![vec![1_i64, 1], vec![1, 0]]);
+
+        assert_eq!(
+            fibonacci.pow(50),
+            Matrix::from_rows(vec![
+                vec![20365011074, 12586269025],
+                vec![12586269025, 7778742049]
+            ])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn pow_3() {
+        let _matrix = Matrix::new(2, 3, 1.0).pow(2);
+    }
+
+    #[test]
+    fn graph_laplacian_1() {
+        let adjacency = Matrix::from_rows(vec![
+            vec![0.0, 1.0, 1.0, 0.0],
+            vec![1.0, 0.0, 2.0, 0.0],
+            vec![1.0, 2.0, 0.0, 0.5],
+            vec![0.0, 0.0, 0.5, 0.0],
+        ]);
+
+        let laplacian = adjacency.graph_laplacian();
+
+        assert_eq!(laplacian.get_diagonal(), vec![2.0, 3.0, 3.5, 0.5]);
+        assert_eq!(*laplacian.get(1, 2), -2.0);
+        assert_eq!(laplacian, laplacian.transpose());
+        for i in 0..4 {
+            assert_eq!(laplacian.get_row(i).iter().sum::<f64>(), 0.0);
+        }
+    }
+
+    #[test]
+    fn kronecker_1() {
+        let matrix1 = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let matrix2 = Matrix::from_rows(vec![vec![0.0, 5.0], vec![6.0, 7.0]]);
+
+        let product = matrix1.kronecker(&matrix2);
+
+        assert_eq!(product.shape(), (4, 4));
+        assert_eq!(product.get_row(0), vec![0.0, 5.0, 0.0, 10.0]);
+        assert_eq!(*product.get(1, 0), 6.0);
+        assert_eq!(*product.get(2, 1), 15.0);
+        assert_eq!(*product.get(3, 2), 24.0);
+        assert_eq!(*product.get(3, 3), 28.0);
+    }
+
+    #[test]
+    fn kronecker_2() {
+        let row = Matrix::from_rows(vec![vec![1.0, -1.0, 2.0]]);
+        let column = Matrix::from_rows(vec![vec![2.0], vec![3.0]]);
+
+        assert_eq!(
+            row.kronecker(&column),
+            Matrix::from_rows(vec![vec![2.0, -2.0, 4.0], vec![3.0, -3.0, 6.0]])
+        );
+    }
+
+    #[test]
+    fn normalized_laplacian_1() {
+        let adjacency = Matrix::from_rows(vec![
+            vec![0.0, 1.0, 1.0],
+            vec![1.0, 0.0, 3.0],
+            vec![1.0, 3.0, 0.0],
+        ]);
+
+        let laplacian = adjacency.normalized_laplacian();
+
+        assert_eq!(laplacian.get_diagonal(), vec![1.0, 1.0, 1.0]);
+        assert!((*laplacian.get(0, 1) + 1.0 / 8.0_f64.sqrt()).abs() < 1e-12);
+        assert!((*laplacian.get(1, 2) + 0.75).abs() < 1e-12);
+        assert!(laplacian.approx_eq(&laplacian.transpose(), 1e-12));
+    }
+
+    #[test]
+    #[should_panic]
+    fn normalized_laplacian_2() {
+        let adjacency = Matrix::from_rows(vec![vec![0.0, 0.0], vec![0.0, 0.0]]);
+        let _laplacian = adjacency.normalized_laplacian();
+    }
+
+    #[test]
+    fn is_symmetric_1() {
+        let symmetric = Matrix::from_rows(vec![
+            vec![4.0, 1.0, 2.0],
+            vec![1.0, 5.0, 3.0],
+            vec![2.0, 3.0, 6.0],
+        ]);
+        let asymmetric = Matrix::from_rows(vec![
+            vec![4.0, 1.0, 2.0],
+            vec![1.0, 5.0, 3.0],
+            vec![2.5, 3.0, 6.0],
+        ]);
+        let mut noisy = symmetric.clone();
+        noisy.set(0, 2, 2.0 + 1e-16);
+
+        assert!(symmetric.is_symmetric());
+        assert!(noisy.is_symmetric());
+        assert!(!asymmetric.is_symmetric());
+        assert!(!Matrix::new(2, 3, 1.0).is_symmetric());
+    }
+
+    #[test]
+    fn is_upper_triangular_1() {
+        let upper = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![0.0, 4.0, 5.0],
+            vec![0.0, 0.0, 6.0],
+        ]);
+
+        assert!(upper.is_upper_triangular());
+        assert!(!upper.is_lower_triangular());
+        assert!(upper.transpose().is_lower_triangular());
+        assert!(!upper.transpose().is_upper_triangular());
+    }
+
+    #[test]
+    fn is_upper_triangular_2() {
+        let identity: Matrix<f64> = Matrix::identity(3);
+        let wide = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![0.0, 4.0, 5.0]]);
+
+        assert!(identity.is_upper_triangular() && identity.is_lower_triangular());
+        assert!(wide.is_upper_triangular());
+        assert!(!wide.is_lower_triangular());
     }
-}
 
-impl<
-        T: Num
-            + NumAssign
-            + Signed
-            + Float
-            + fmt::Display
-            + Copy
-            + PartialEq
-            + Debug
-            + std::iter::Product<T>,
-    > fmt::Display for Matrix<T>
-{
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut display = String::new();
-        let mut index = 0;
-        for _i in 0..self.columns {
-            display += "{";
-            for _k in 0..self.rows {
-                display += &format!(" {},", self.data[index]);
-                index += 1;
+    #[test]
+    fn connected_components_1() {
+        // Blocks {0, 1, 2} and {3, 4}
+        let adjacency = Matrix::from_rows(vec![
+            vec![0.0, 1.0, 0.0, 0.0, 0.0],
+            vec![1.0, 0.0, 2.0, 0.0, 0.0],
+            vec![0.0, 2.0, 0.0, 0.0, 0.0],
+            vec![0.0, 0.0, 0.0, 0.0, 1.0],
+            vec![0.0, 0.0, 0.0, 1.0, 0.0],
+        ]);
+
+        assert_eq!(adjacency.connected_components(0.0), 2);
+        assert_eq!(adjacency.connected_components(1.5), 4);
+        assert_eq!(Matrix::new(3, 3, 0.0).connected_components(0.0), 3);
+    }
+
+    #[test]
+    fn connected_components_2() {
+        // Matches the multiplicity of the zero Laplacian eigenvalue
+        let adjacency = Matrix::from_rows(vec![
+            vec![0.0, 1.0, 0.0, 0.0],
+            vec![1.0, 0.0, 0.0, 0.0],
+            vec![0.0, 0.0, 0.0, 3.0],
+            vec![0.0, 0.0, 3.0, 0.0],
+        ]);
+
+        let (values, _) = adjacency.graph_laplacian().eig_symmetric().unwrap();
+        let zeros = values.iter().filter(|value| value.abs() < 1e-9).count();
+
+        assert_eq!(adjacency.connected_components(0.0), zeros);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn par_mul_1() {
+        let mut matrix1 = Matrix::zeros(37, 23);
+        let mut matrix2 = Matrix::zeros(23, 41);
+        for i in 0..37 {
+            for k in 0..23 {
+                matrix1.set(i, k, ((i * 7 + k * 3) % 11) as f64 - 5.0);
             }
-            display += " }\n";
         }
-        write!(f, "{}", display)
+        for i in 0..23 {
+            for k in 0..41 {
+                matrix2.set(i, k, ((i * 5 + k) % 13) as f64 * 0.5);
+            }
+        }
+
+        assert_eq!(matrix1.par_mul(&matrix2), &matrix1 * &matrix2);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[cfg(feature = "rayon")]
+    #[test]
+    #[should_panic]
+    fn par_mul_2() {
+        let _matrix = Matrix::new(2, 3, 1.0).par_mul(&Matrix::new(2, 3, 1.0));
+    }
 
     #[test]
-    fn sum_two_matrix_1() {
-        let mut matrix1 = Matrix::new(4, 4, 2.0);
-        let matrix2 = Matrix::new(4, 4, 3.0);
-        let result_matrix = Matrix::new(4, 4, 5.0);
+    fn reachability_1() {
+        // Directed path 0 -> 1 -> 2 -> 3
+        let mut adjacency = Matrix::zeros(4, 4);
+        adjacency.set(0, 1, 1.0);
+        adjacency.set(1, 2, 1.0);
+        adjacency.set(2, 3, 1.0);
 
-        matrix1 = matrix1 + matrix2;
+        let two_steps = adjacency.reachability(2);
+        let three_steps = adjacency.reachability(3);
 
-        assert_eq!(matrix1, result_matrix);
+        assert_eq!(
+            two_steps,
+            Matrix::from_rows(vec![
+                vec![0.0, 1.0, 1.0, 0.0],
+                vec![0.0, 0.0, 1.0, 1.0],
+                vec![0.0, 0.0, 0.0, 1.0],
+                vec![0.0, 0.0, 0.0, 0.0],
+            ])
+        );
+        assert_eq!(*three_steps.get(0, 3), 1.0);
+        assert_eq!(three_steps, adjacency.reachability(10));
+        assert_eq!(adjacency.reachability(0), Matrix::zeros(4, 4));
     }
 
     #[test]
-    fn sum_two_matrix_2() {
-        let mut matrix1 = Matrix::new(4, 4, 2.0);
-        let mut matrix2 = Matrix::new(4, 4, 0.0);
-        let mut result_matrix = Matrix::new(4, 4, 2.0);
+    fn mult_matrix_3() {
+        // Same data as mult_matrix_2, through the row-major kernel
+        let matrix1 = Matrix::from_rows(vec![
+            vec![6.0, 8.0, 9.0, 5.0],
+            vec![3.0, 8.0, 4.0, 7.0],
+            vec![4.0, 5.0, 6.0, 4.0],
+            vec![6.0, 2.0, 2.0, 9.0],
+        ]);
+        let matrix2 = Matrix::from_rows(vec![
+            vec![7.0, 6.0, 1.0],
+            vec![6.0, 4.0, 8.0],
+            vec![3.0, 0.0, 6.0],
+            vec![1.0, 1.0, 1.0],
+        ]);
+        let expected = Matrix::from_rows(vec![
+            vec![122.0, 73.0, 129.0],
+            vec![88.0, 57.0, 98.0],
+            vec![80.0, 48.0, 84.0],
+            vec![69.0, 53.0, 43.0],
+        ]);
 
-        result_matrix.set(0, 0, 9.0);
-        matrix2.set(0, 0, 7.0);
-        matrix1 = matrix1 + matrix2;
+        assert_eq!(matrix1.checked_mul(&matrix2), Ok(expected.clone()));
+        assert_eq!(&matrix1 * &matrix2, expected);
+    }
 
-        assert_eq!(matrix1, result_matrix);
+    #[test]
+    fn mult_matrix_4() {
+        let matrix1 = Matrix::new(3, 0, 1.0);
+        let matrix2 = Matrix::new(0, 2, 1.0);
+        let matrix3 = Matrix::new(2, 0, 1.0);
+
+        assert_eq!(&matrix1 * &matrix2, Matrix::new(3, 2, 0.0));
+        assert_eq!((&matrix2 * &matrix3).shape(), (0, 0));
+    }
+
+    #[test]
+    fn suggested_tolerance_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, -4.0, 2.0], vec![0.5, 3.0, -1.0]]);
+
+        let tolerance = matrix.suggested_tolerance();
+
+        assert_eq!(tolerance, 3.0 * f64::EPSILON * 4.0);
+        assert_eq!(
+            matrix.scale(1000.0).suggested_tolerance(),
+            1000.0 * tolerance
+        );
+        assert_eq!(Matrix::new(2, 2, 0.0).suggested_tolerance(), 0.0);
+    }
+
+    #[test]
+    fn determinant_cofactor_1() {
+        let matrix2 = Matrix::from_rows(vec![vec![3.0, 8.0], vec![4.0, 6.0]]);
+        let matrix3 = Matrix::from_rows(vec![
+            vec![6.0, 1.0, 1.0],
+            vec![4.0, -2.0, 5.0],
+            vec![2.0, 8.0, 7.0],
+        ]);
+
+        assert_eq!(matrix2.determinant_cofactor(), -14.0);
+        assert!((matrix2.determinant_cofactor() - matrix2.get_determinant()).abs() < 1e-12);
+        assert_eq!(matrix3.determinant_cofactor(), -306.0);
+        assert!((matrix3.determinant_cofactor() - matrix3.get_determinant()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn determinant_cofactor_2() {
+        let matrix = Matrix::from_rows(vec![
+            vec![2_i64, 0, 1, 3],
+            vec![1, -1, 4, 0],
+            vec![0, 5, 2, 1],
+            vec![3, 1, 0, 2],
+        ]);
+
+        assert_eq!(matrix.determinant_cofactor(), 130);
     }
 
     #[test]
     #[should_panic]
-    fn sum_two_matrix_3() {
-        let mut matrix1 = Matrix::new(4, 4, 2.0);
-        let matrix2 = Matrix::new(4, 5, 0.0);
+    fn determinant_cofactor_3() {
+        let _determinant = Matrix::new(2, 3, 1.0).determinant_cofactor();
+    }
 
-        matrix1 = matrix1 + matrix2;
+    #[test]
+    fn perron_vector_1() {
+        // Eigenvalues 5 and 2, with (1, 2) the dominant eigenvector
+        let matrix = Matrix::from_rows(vec![vec![3.0, 1.0], vec![2.0, 4.0]]);
+
+        let vector = matrix.perron_vector(1000, 1e-14).unwrap();
+
+        assert!((vector[0] - 1.0 / 3.0).abs() < 1e-12);
+        assert!((vector[1] - 2.0 / 3.0).abs() < 1e-12);
+        let image = matrix.mul_vec(&vector);
+        assert!((image[0] - 5.0 * vector[0]).abs() < 1e-12);
     }
 
     #[test]
-    fn substract_two_matrix_1() {
-        let mut matrix1 = Matrix::new(4, 4, 2.0);
-        let matrix2 = Matrix::new(4, 4, 3.0);
-        let result_matrix = Matrix::new(4, 4, -1.0);
+    fn perron_vector_2() {
+        // A periodic permutation matrix still converges thanks to the shift
+        let cycle = Matrix::from_rows(vec![
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+            vec![1.0, 0.0, 0.0],
+        ]);
+        let negative = Matrix::from_rows(vec![vec![1.0, -1.0], vec![1.0, 1.0]]);
 
-        matrix1 = matrix1 - matrix2;
+        let vector = cycle.perron_vector(10, 1e-14).unwrap();
 
-        assert_eq!(matrix1, result_matrix);
+        assert!(vector.iter().all(|value| (value - 1.0 / 3.0).abs() < 1e-12));
+        assert_eq!(negative.perron_vector(1000, 1e-12), None);
     }
 
     #[test]
-    fn substract_two_matrix_2() {
-        let mut matrix1 = Matrix::new(4, 4, 2.0);
-        let mut matrix2 = Matrix::new(4, 4, 0.0);
-        let mut result_matrix = Matrix::new(4, 4, 2.0);
+    fn adjugate_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![6.0, 1.0, 1.0],
+            vec![4.0, -2.0, 5.0],
+            vec![2.0, 8.0, 7.0],
+        ]);
 
-        result_matrix.set(0, 0, -5.0);
-        matrix2.set(0, 0, 7.0);
-        matrix1 = matrix1 - matrix2;
+        let product = &matrix * &matrix.adjugate();
 
-        assert_eq!(matrix1, result_matrix);
+        let identity: Matrix<f64> = Matrix::identity(3);
+        assert!(product.approx_eq(&identity.scale(matrix.get_determinant()), 1e-9));
+        assert_eq!(matrix.adjugate(), matrix.cofactor_matrix().transpose());
+    }
+
+    #[test]
+    fn cofactor_matrix_1() {
+        let matrix = Matrix::from_rows(vec![vec![1_i64, 2], vec![3, 4]]);
+
+        assert_eq!(
+            matrix.cofactor_matrix(),
+            Matrix::from_rows(vec![vec![4, -3], vec![-2, 1]])
+        );
+        assert_eq!(
+            matrix.adjugate(),
+            Matrix::from_rows(vec![vec![4, -2], vec![-3, 1]])
+        );
     }
 
     #[test]
     #[should_panic]
-    fn substract_two_matrix_3() {
-        let mut matrix1 = Matrix::new(4, 4, 2.0);
-        let matrix2 = Matrix::new(4, 5, 0.0);
+    fn adjugate_2() {
+        let _adjugate = Matrix::new(3, 2, 1.0).adjugate();
+    }
 
-        matrix1 = matrix1 - matrix2;
+    #[test]
+    fn pagerank_1() {
+        // Links: 0 -> 1, 0 -> 2, 1 -> 2, 2 -> 0, 3 -> 2, so page 2 collects
+        // the most links, then page 0 which page 2 links to
+        let transition = Matrix::from_rows(vec![
+            vec![0.0, 0.0, 1.0, 0.0],
+            vec![0.5, 0.0, 0.0, 0.0],
+            vec![0.5, 1.0, 0.0, 1.0],
+            vec![0.0, 0.0, 0.0, 0.0],
+        ]);
+
+        let ranks = transition.pagerank(0.85, 1000, 1e-12).unwrap();
+
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(ranks[2] > ranks[0]);
+        assert!(ranks[0] > ranks[1]);
+        assert!(ranks[1] > ranks[3]);
+        assert!((ranks[3] - 0.15 / 4.0).abs() < 1e-12);
     }
 
     #[test]
-    fn get_row_1() {
-        let mut matrix1 = Matrix::new(3, 4, 0.0);
-        matrix1.set(0, 0, 8.0);
-        matrix1.set(0, 1, 7.0);
-        matrix1.set(0, 2, 9.0);
-        matrix1.set(0, 3, 6.0);
+    fn pagerank_2() {
+        // Dangling page 1 has no links, ranks still sum to one
+        let transition = Matrix::from_rows(vec![vec![0.0, 0.0], vec![1.0, 0.0]]);
 
-        let data = vec![8.0, 7.0, 9.0, 6.0];
+        let ranks = transition.pagerank(0.85, 1000, 1e-12).unwrap();
 
-        assert_eq!(matrix1.get_row(0), data);
+        assert!((ranks.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(ranks[1] > ranks[0]);
+        assert_eq!(transition.pagerank(0.85, 1, 1e-12), None);
     }
 
     #[test]
-    fn get_row_2() {
-        let mut matrix1 = Matrix::new(3, 4, 0.0);
+    fn iter_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
 
-        matrix1.set(2, 0, 8.0);
-        matrix1.set(2, 1, 7.0);
-        matrix1.set(2, 2, 9.0);
-        matrix1.set(2, 3, 6.0);
+        assert_eq!(matrix.iter().sum::<f64>(), 21.0);
+        assert_eq!(
+            matrix.iter().copied().collect::<Vec<f64>>(),
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+        );
+    }
 
-        let data = vec![8.0, 7.0, 9.0, 6.0];
+    #[test]
+    fn iter_rows_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
+
+        assert_eq!(matrix.iter_rows().count(), 3);
+        assert_eq!(matrix.iter_rows().nth(1), Some(&[3.0, 4.0][..]));
+        assert_eq!(Matrix::new(2, 0, 1.0).iter_rows().count(), 2);
+    }
+
+    #[test]
+    fn mul_into_1() {
+        let matrix1 = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        let matrix2 = Matrix::from_rows(vec![vec![1.0, -1.0], vec![0.5, 2.0], vec![3.0, 0.0]]);
+        let mut out = Matrix::new(2, 2, 7.0);
+
+        matrix1.mul_into(&matrix2, &mut out);
+
+        assert_eq!(out, &matrix1 * &matrix2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_into_2() {
+        let mut out = Matrix::new(2, 3, 0.0);
+        Matrix::new(2, 3, 1.0).mul_into(&Matrix::new(3, 2, 1.0), &mut out);
+    }
+
+    #[test]
+    fn reshape_1() {
+        let mut matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0, 10.0, 11.0, 12.0],
+        ]);
+
+        let reshaped = matrix.reshaped(3, 4);
+        matrix.reshape(3, 4);
+
+        assert_eq!(matrix, reshaped);
+        assert_eq!(matrix.shape(), (3, 4));
+        assert_eq!(matrix.get_row(1), vec![5.0, 6.0, 7.0, 8.0]);
+        assert_eq!(*matrix.get(2, 3), 12.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn reshape_2() {
+        let mut matrix = Matrix::new(2, 6, 1.0);
+        matrix.reshape(5, 2);
+    }
+
+    #[test]
+    fn gemm_1() {
+        let a = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        let b = Matrix::from_rows(vec![vec![1.0, -1.0], vec![0.5, 2.0], vec![3.0, 0.0]]);
+        let c = Matrix::from_rows(vec![vec![1.0, 2.0], vec![-3.0, 0.25]]);
+
+        let mut result = c.clone();
+        result.gemm(2.0, &a, &b, -0.5);
+
+        let expected = (&a * &b).scale(2.0) + c.scale(-0.5);
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn gemm_2() {
+        let mut c = Matrix::new(3, 3, 0.0);
+        c.gemm(1.0, &Matrix::new(2, 2, 1.0), &Matrix::new(2, 3, 1.0), 1.0);
+    }
+
+    #[test]
+    fn qr_decompose_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![12.0, -51.0, 4.0],
+            vec![6.0, 167.0, -68.0],
+            vec![-4.0, 24.0, -41.0],
+            vec![1.0, 2.0, 3.0],
+        ]);
+
+        let (q, r) = matrix.qr_decompose().unwrap();
+
+        let identity: Matrix<f64> = Matrix::identity(3);
+        assert_eq!(q.shape(), (4, 3));
+        assert!(r.is_upper_triangular());
+        assert!((&q * &r).approx_eq(&matrix, 1e-10));
+        assert!((&q.transpose() * &q).approx_eq(&identity, 1e-12));
+    }
+
+    #[test]
+    fn qr_decompose_2() {
+        let dependent = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0], vec![3.0, 6.0]]);
+        let wide = Matrix::new(2, 3, 1.0);
+
+        assert_eq!(dependent.qr_decompose(), None);
+        assert_eq!(wide.qr_decompose(), None);
+    }
+
+    #[test]
+    fn mul_tiled_1() {
+        let mut matrix1 = Matrix::zeros(16, 16);
+        let mut matrix2 = Matrix::zeros(16, 16);
+        for i in 0..16 {
+            for k in 0..16 {
+                matrix1.set(i, k, ((i * 3 + k * 7) % 10) as f64 - 4.5);
+                matrix2.set(i, k, ((i * 11 + k * 5) % 9) as f64 * 0.25);
+            }
+        }
+
+        let expected = &matrix1 * &matrix2;
+
+        assert_eq!(matrix1.mul_tiled(&matrix2, 4), expected);
+        assert_eq!(matrix1.mul_tiled(&matrix2, 5), expected);
+        assert_eq!(matrix1.mul_tiled(&matrix2, 1), expected);
+        assert_eq!(matrix1.mul_tiled(&matrix2, 64), expected);
+    }
+
+    #[test]
+    fn mul_tiled_2() {
+        let matrix1 = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+        let matrix2 = Matrix::from_rows(vec![vec![1.0, -1.0], vec![0.5, 2.0], vec![3.0, 0.0]]);
+
+        assert_eq!(matrix1.mul_tiled(&matrix2, 2), &matrix1 * &matrix2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn mul_tiled_3() {
+        let _matrix = Matrix::new(2, 2, 1.0).mul_tiled(&Matrix::new(3, 2, 1.0), 2);
+    }
+
+    #[test]
+    fn cholesky_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![4.0, 12.0, -16.0],
+            vec![12.0, 37.0, -43.0],
+            vec![-16.0, -43.0, 98.0],
+        ]);
+
+        let lower = matrix.cholesky().unwrap();
+
+        assert!(lower.is_lower_triangular());
+        assert_eq!(
+            lower,
+            Matrix::from_rows(vec![
+                vec![2.0, 0.0, 0.0],
+                vec![6.0, 1.0, 0.0],
+                vec![-8.0, 5.0, 3.0],
+            ])
+        );
+        assert!((&lower * &lower.transpose()).approx_eq(&matrix, 1e-12));
+    }
+
+    #[test]
+    fn cholesky_2() {
+        let indefinite = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 1.0]]);
+        let asymmetric = Matrix::from_rows(vec![vec![2.0, 1.0], vec![0.0, 2.0]]);
+
+        assert_eq!(indefinite.cholesky(), None);
+        assert_eq!(asymmetric.cholesky(), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cholesky_3() {
+        let _lower = Matrix::new(2, 3, 1.0).cholesky();
+    }
+
+    #[test]
+    fn get_unchecked_1() {
+        let mut matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+        for i in 0..2 {
+            for k in 0..3 {
+                // Indices stay within the 2x3 shape
+                assert_eq!(unsafe { matrix.get_unchecked(i, k) }, matrix.get(i, k));
+            }
+        }
+
+        unsafe {
+            *matrix.get_unchecked_mut(1, 2) = 10.0;
+        }
+        assert_eq!(*matrix.get(1, 2), 10.0);
+    }
+
+    #[test]
+    fn power_iteration_1() {
+        // Eigenvalues 5 and 2, with (1, 2) the dominant eigenvector
+        let matrix = Matrix::from_rows(vec![vec![3.0, 1.0], vec![2.0, 4.0]]);
+
+        let (value, vector) = matrix.power_iteration(1000, 1e-14).unwrap();
+
+        assert!((value - 5.0).abs() < 1e-10);
+        assert!((vector[1] / vector[0] - 2.0).abs() < 1e-10);
+        let length: f64 = vector.iter().map(|x| x * x).sum();
+        assert!((length - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn power_iteration_2() {
+        // The dominant eigenvalue -4 flips the sign of every iterate
+        let matrix = Matrix::from_rows(vec![vec![-4.0, 0.0], vec![1.0, 1.0]]);
+
+        let (value, _vector) = matrix.power_iteration(1000, 1e-14).unwrap();
+
+        assert!((value + 4.0).abs() < 1e-10);
+        assert_eq!(Matrix::new(2, 3, 1.0).power_iteration(10, 1e-9), None);
+    }
+
+    #[test]
+    fn memory_bytes_1() {
+        let matrix = Matrix::new(100, 100, 0.0_f64);
+
+        let bytes = matrix.memory_bytes();
+
+        assert!(bytes >= 80000);
+        assert!(bytes <= 80000 + std::mem::size_of::<Matrix<f64>>());
+    }
+
+    #[test]
+    fn shrink_to_fit_1() {
+        let mut matrix = Matrix::new(100, 100, 1.0);
+        // Downsize to the first 10 rows, keeping the old allocation
+        matrix.data.truncate(1000);
+        matrix.reshape(10, 100);
+        let before = matrix.memory_bytes();
+
+        matrix.shrink_to_fit();
+
+        assert!(matrix.data.capacity() < 10000);
+        assert!(matrix.memory_bytes() < before);
+        assert_eq!(matrix, Matrix::new(10, 100, 1.0));
+    }
+
+    #[test]
+    fn transpose_in_place_1() {
+        let mut matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ]);
+        let expected = matrix.transpose();
+
+        matrix.transpose_in_place();
+
+        assert_eq!(matrix, expected);
+    }
+
+    #[test]
+    #[should_panic]
+    fn transpose_in_place_2() {
+        let mut matrix = Matrix::new(2, 3, 1.0);
+        matrix.transpose_in_place();
+    }
+
+    #[test]
+    fn reserve_1() {
+        let mut matrix = Matrix::new(2, 3, 1.0);
+
+        matrix.reserve(60);
+
+        assert!(matrix.data.capacity() >= 66);
+        assert_eq!(matrix, Matrix::new(2, 3, 1.0));
+    }
+
+    #[test]
+    fn add_scalar_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, -2.0], vec![0.5, 4.0]]);
+
+        assert_eq!(
+            matrix.add_scalar(10.0),
+            Matrix::from_rows(vec![vec![11.0, 8.0], vec![10.5, 14.0]])
+        );
+        assert_eq!(matrix.add_scalar(10.0).sub_scalar(10.0), matrix);
+    }
+
+    #[test]
+    fn sub_scalar_1() {
+        let matrix = Matrix::from_rows(vec![vec![1, 2, 3], vec![4, 5, 6]]);
+
+        assert_eq!(
+            matrix.sub_scalar(1),
+            Matrix::from_rows(vec![vec![0, 1, 2], vec![3, 4, 5]])
+        );
+    }
+
+    #[test]
+    fn try_exchange_rows_1() {
+        let mut matrix = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+
+        assert_eq!(
+            matrix.try_exchange_rows(0, 2),
+            Err(MatrixError::RowOutOfBounds { row: 2, rows: 2 })
+        );
+        assert_eq!(
+            matrix,
+            Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0]])
+        );
+        assert_eq!(matrix.try_exchange_rows(1, 0), Ok(()));
+        assert_eq!(matrix.get_row(0), vec![3.0, 4.0]);
+    }
+
+    #[test]
+    fn try_exchange_columns_1() {
+        let mut matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0]]);
+
+        assert_eq!(
+            matrix.try_exchange_columns(5, 0),
+            Err(MatrixError::ColumnOutOfBounds {
+                column: 5,
+                columns: 3
+            })
+        );
+        assert_eq!(matrix.try_exchange_columns(0, 2), Ok(()));
+        assert_eq!(matrix.get_row(0), vec![3.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn row_out_of_bounds_1() {
+        let error = MatrixError::RowOutOfBounds { row: 4, rows: 3 };
+
+        assert_eq!(
+            error.to_string(),
+            "Row index is out of bounds: got 4, but the matrix has 3 rows."
+        );
+    }
+
+    #[test]
+    fn push_row_1() {
+        let mut matrix = Matrix::zeros(0, 0);
+
+        matrix.push_row(vec![1.0, 2.0, 3.0]);
+        matrix.push_row(vec![4.0, 5.0, 6.0]);
 
-        assert_eq!(matrix1.get_row(2), data);
+        assert_eq!(matrix.shape(), (2, 3));
+        assert_eq!(
+            matrix,
+            Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]])
+        );
     }
 
     #[test]
     #[should_panic]
-    fn get_row_3() {
-        let matrix1 = Matrix::new(3, 4, 0.0);
-
-        let _data = matrix1.get_row(4);
+    fn push_row_2() {
+        let mut matrix = Matrix::new(2, 3, 1.0);
+        matrix.push_row(vec![1.0, 2.0]);
     }
 
     #[test]
-    fn get_column_1() {
-        let mut matrix1 = Matrix::new(4, 3, 0.0);
-        matrix1.set(0, 0, 8.0);
-        matrix1.set(1, 0, 7.0);
-        matrix1.set(2, 0, 9.0);
-        matrix1.set(3, 0, 6.0);
+    fn push_column_1() {
+        let mut matrix = Matrix::from_rows(vec![vec![1.0, 2.0], vec![4.0, 5.0]]);
 
-        let data = vec![8.0, 7.0, 9.0, 6.0];
+        matrix.push_column(vec![3.0, 6.0]);
 
-        assert_eq!(matrix1.get_column(0), data);
+        assert_eq!(
+            matrix,
+            Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]])
+        );
+
+        let mut built = Matrix::zeros(0, 0);
+        built.push_column(vec![1.0, 4.0]);
+        built.push_column(vec![2.0, 5.0]);
+        assert_eq!(
+            built,
+            Matrix::from_rows(vec![vec![1.0, 2.0], vec![4.0, 5.0]])
+        );
     }
 
     #[test]
-    fn get_column_2() {
-        let mut matrix1 = Matrix::new(4, 3, 0.0);
+    #[should_panic]
+    fn push_column_2() {
+        let mut matrix = Matrix::new(2, 3, 1.0);
+        matrix.push_column(vec![1.0, 2.0, 3.0]);
+    }
 
-        matrix1.set(0, 2, 8.0);
-        matrix1.set(1, 2, 7.0);
-        matrix1.set(2, 2, 9.0);
-        matrix1.set(3, 2, 6.0);
+    #[test]
+    fn reverse_rows_1() {
+        let mut matrix = Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0, 4.0], vec![5.0, 6.0]]);
 
-        let data = vec![8.0, 7.0, 9.0, 6.0];
+        matrix.reverse_rows();
 
-        assert_eq!(matrix1.get_column(2), data);
+        assert_eq!(
+            matrix,
+            Matrix::from_rows(vec![vec![5.0, 6.0], vec![3.0, 4.0], vec![1.0, 2.0]])
+        );
     }
 
     #[test]
-    #[should_panic]
-    fn get_column_3() {
-        let matrix1 = Matrix::new(4, 4, 0.0);
+    fn reverse_columns_1() {
+        let mut matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
 
-        let _data = matrix1.get_column(4);
+        matrix.reverse_columns();
+
+        assert_eq!(
+            matrix,
+            Matrix::from_rows(vec![vec![3.0, 2.0, 1.0], vec![6.0, 5.0, 4.0]])
+        );
     }
 
     #[test]
-    fn mult_matrix_1() {
-        let mut matrix1 = Matrix::new(4, 4, 2.0);
-        let matrix2 = Matrix::new(4, 4, 2.0);
-        let result_matrix = Matrix::new(4, 4, 16.0);
-
-        matrix1 = matrix1 * matrix2;
+    fn get_determinant_5() {
+        // Zero first pivot, resolved by exchanging the first two rows
+        let matrix = Matrix::from_rows(vec![
+            vec![0.0, 2.0, 1.0],
+            vec![1.0, 1.0, 0.0],
+            vec![3.0, 0.0, 1.0],
+        ]);
 
-        assert_eq!(matrix1, result_matrix);
+        assert_eq!(matrix.get_determinant(), -5.0);
+        assert_eq!(matrix.determinant_cofactor(), -5.0);
     }
 
     #[test]
-    fn mult_matrix_2() {
-        let mut matrix1 = Matrix::new(4, 4, 2.0);
-        let mut matrix2 = Matrix::new(4, 3, 4.0);
-        let mut result_matrix = Matrix::new(4, 3, 0.0);
+    fn get_determinant_6() {
+        // The second pivot only turns zero during elimination
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![2.0, 4.0, 5.0],
+            vec![1.0, 3.0, 4.0],
+        ]);
 
-        matrix1.set(0, 0, 6.0);
-        matrix1.set(0, 1, 8.0);
-        matrix1.set(0, 2, 9.0);
-        matrix1.set(0, 3, 5.0);
-        matrix1.set(1, 0, 3.0);
-        matrix1.set(1, 1, 8.0);
-        matrix1.set(1, 2, 4.0);
-        matrix1.set(1, 3, 7.0);
-        matrix1.set(2, 0, 4.0);
-        matrix1.set(2, 1, 5.0);
-        matrix1.set(2, 2, 6.0);
-        matrix1.set(2, 3, 4.0);
-        matrix1.set(3, 0, 6.0);
-        matrix1.set(3, 1, 2.0);
-        matrix1.set(3, 2, 2.0);
-        matrix1.set(3, 3, 9.0);
+        assert_eq!(matrix.get_determinant(), 1.0);
+    }
 
-        matrix2.set(0, 0, 7.0);
-        matrix2.set(0, 1, 6.0);
-        matrix2.set(0, 2, 1.0);
-        matrix2.set(1, 0, 6.0);
-        matrix2.set(1, 1, 4.0);
-        matrix2.set(1, 2, 8.0);
-        matrix2.set(2, 0, 3.0);
-        matrix2.set(2, 1, 0.0);
-        matrix2.set(2, 2, 6.0);
-        matrix2.set(3, 0, 1.0);
-        matrix2.set(3, 1, 1.0);
-        matrix2.set(3, 2, 1.0);
+    #[test]
+    fn get_determinant_7() {
+        // Two exchanges cancel out: rows (2, 0, 1) of an upper triangular
+        // matrix with determinant 24
+        let matrix = Matrix::from_rows(vec![
+            vec![0.0, 0.0, 4.0],
+            vec![2.0, 5.0, 1.0],
+            vec![0.0, 3.0, 7.0],
+        ]);
 
-        result_matrix.set(0, 0, 122.0);
-        result_matrix.set(0, 1, 73.0);
-        result_matrix.set(0, 2, 129.0);
-        result_matrix.set(1, 0, 88.0);
-        result_matrix.set(1, 1, 57.0);
-        result_matrix.set(1, 2, 98.0);
-        result_matrix.set(2, 0, 80.0);
-        result_matrix.set(2, 1, 48.0);
-        result_matrix.set(2, 2, 84.0);
-        result_matrix.set(3, 0, 69.0);
-        result_matrix.set(3, 1, 53.0);
-        result_matrix.set(3, 2, 43.0);
+        assert_eq!(matrix.get_determinant(), 24.0);
+    }
 
-        matrix1 = matrix1 * matrix2;
+    #[test]
+    fn get_determinant_8() {
+        // A zero first column can't be fixed by row exchanges, which is
+        // right: the determinant is zero, so no column exchange is needed
+        let matrix = Matrix::from_rows(vec![
+            vec![0.0, 1.0, 2.0],
+            vec![0.0, 3.0, 4.0],
+            vec![0.0, 5.0, 7.0],
+        ]);
 
-        assert_eq!(result_matrix, matrix1);
+        assert_eq!(matrix.get_determinant(), 0.0);
     }
 
     #[test]
-    fn exchange_rows_1() {
-        let mut matrix = Matrix::new(3, 4, 5.0);
-        matrix.set(0, 0, 1.0);
-        matrix.set(0, 1, 2.0);
-        matrix.set(0, 2, 3.0);
-        matrix.set(0, 3, 4.0);
-        matrix.set(1, 0, 5.0);
-        matrix.set(1, 1, 6.0);
-        matrix.set(1, 2, 7.0);
-        matrix.set(1, 3, 8.0);
+    fn roll_rows_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, 5.0, 6.0],
+            vec![7.0, 8.0, 9.0],
+        ]);
 
-        matrix.exchange_rows(0, 1);
+        let rolled = matrix.roll_rows(1);
 
-        let row1 = vec![1.0, 2.0, 3.0, 4.0];
-        let row2 = vec![5.0, 6.0, 7.0, 8.0];
+        assert_eq!(
+            rolled,
+            Matrix::from_rows(vec![
+                vec![7.0, 8.0, 9.0],
+                vec![1.0, 2.0, 3.0],
+                vec![4.0, 5.0, 6.0],
+            ])
+        );
+        assert_eq!(rolled.roll_rows(-1), matrix);
+        assert_eq!(matrix.roll_rows(3), matrix);
+        assert_eq!(matrix.roll_rows(-4), matrix.roll_rows(2));
+    }
 
-        assert_eq!(row1, matrix.get_row(1));
-        assert_eq!(row2, matrix.get_row(0));
+    #[test]
+    fn roll_columns_1() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0]]);
+
+        assert_eq!(
+            matrix.roll_columns(-1),
+            Matrix::from_rows(vec![vec![2.0, 3.0, 1.0], vec![5.0, 6.0, 4.0]])
+        );
+        assert_eq!(matrix.roll_columns(4), matrix.roll_columns(1));
     }
 
     #[test]
-    fn exchange_rows_2() {
-        let mut matrix = Matrix::new(3, 4, 5.0);
+    fn as_slice_1() {
+        let mut matrix = Matrix::zeros(2, 2);
         matrix.set(0, 0, 1.0);
         matrix.set(0, 1, 2.0);
-        matrix.set(0, 2, 3.0);
-        matrix.set(0, 3, 4.0);
-        matrix.set(2, 0, 5.0);
-        matrix.set(2, 1, 6.0);
-        matrix.set(2, 2, 7.0);
-        matrix.set(2, 3, 8.0);
+        matrix.set(1, 0, 3.0);
+        matrix.set(1, 1, 4.0);
 
-        matrix.exchange_rows(0, 2);
+        assert_eq!(matrix.as_slice(), &[1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(matrix.to_vec(), vec![1.0, 2.0, 3.0, 4.0]);
+    }
 
-        let row1 = vec![1.0, 2.0, 3.0, 4.0];
-        let row2 = vec![5.0, 6.0, 7.0, 8.0];
+    #[test]
+    fn determinant_spd_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![4.0, 12.0, -16.0],
+            vec![12.0, 37.0, -43.0],
+            vec![-16.0, -43.0, 98.0],
+        ]);
 
-        assert_eq!(row1, matrix.get_row(2));
-        assert_eq!(row2, matrix.get_row(0));
+        let determinant = matrix.determinant_spd().unwrap();
+
+        assert!((determinant - 36.0).abs() < 1e-9);
+        assert!((determinant - matrix.get_determinant()).abs() < 1e-9);
     }
 
     #[test]
-    fn exchange_columns_1() {
-        let mut matrix = Matrix::new(3, 4, 5.0);
-        matrix.set(0, 0, 1.0);
-        matrix.set(1, 0, 2.0);
-        matrix.set(2, 0, 3.0);
-        matrix.set(0, 1, 4.0);
-        matrix.set(1, 1, 5.0);
-        matrix.set(2, 1, 6.0);
+    fn determinant_spd_2() {
+        let indefinite = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 1.0]]);
 
-        matrix.exchange_columns(0, 1);
+        let singular = Matrix::from_rows(vec![vec![2.0, 2.0], vec![2.0, 2.0]]);
 
-        let column1 = vec![1.0, 2.0, 3.0];
-        let column2 = vec![4.0, 5.0, 6.0];
+        assert_eq!(indefinite.determinant_spd(), None);
+        assert_eq!(singular.determinant_spd(), None);
+        assert_eq!(Matrix::new(2, 3, 1.0).determinant_spd(), None);
+    }
 
-        assert_eq!(column1, matrix.get_column(1));
-        assert_eq!(column2, matrix.get_column(0));
+    #[test]
+    fn row_dot_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, -5.0, 6.0],
+            vec![0.5, 0.0, 2.0],
+        ]);
+
+        assert_eq!(matrix.row_dot(0, 1), 12.0);
+        assert_eq!(matrix.row_dot(1, 2), 14.0);
+        assert_eq!(matrix.row_dot(0, 0), 14.0);
     }
 
     #[test]
-    fn exchange_columns_2() {
-        let mut matrix = Matrix::new(3, 4, 5.0);
-        matrix.set(0, 0, 1.0);
-        matrix.set(1, 0, 2.0);
-        matrix.set(2, 0, 3.0);
-        matrix.set(0, 3, 4.0);
-        matrix.set(1, 3, 5.0);
-        matrix.set(2, 3, 6.0);
+    fn column_dot_1() {
+        let matrix = Matrix::from_rows(vec![
+            vec![1.0, 2.0, 3.0],
+            vec![4.0, -5.0, 6.0],
+            vec![0.5, 0.0, 2.0],
+        ]);
 
-        matrix.exchange_columns(0, 3);
+        assert_eq!(matrix.column_dot(0, 2), 28.0);
+        assert_eq!(matrix.column_dot(1, 1), 29.0);
+    }
 
-        let column1 = vec![1.0, 2.0, 3.0];
-        let column2 = vec![4.0, 5.0, 6.0];
+    #[test]
+    #[should_panic]
+    fn row_dot_2() {
+        let _dot = Matrix::new(2, 2, 1.0).row_dot(0, 2);
+    }
 
-        assert_eq!(column1, matrix.get_column(3));
-        assert_eq!(column2, matrix.get_column(0));
+    #[test]
+    #[should_panic]
+    fn column_dot_2() {
+        let _dot = Matrix::new(2, 2, 1.0).column_dot(2, 0);
     }
 
     #[test]
-    fn set_row_1() {
-        let mut matrix = Matrix::new(3, 4, 5.0);
-        let vec1 = vec![1.0, 2.0, 3.0, 4.0];
-        matrix.set_row(0, vec1);
+    fn running_mean_3() {
+        let mut running = RunningMeanMatrix::new(1, 1);
+        for value in [1.0_f32, 2.0, 2.0, 2.0] {
+            running.update(&Matrix::new(1, 1, value));
+        }
 
-        let vec2 = vec![1.0, 2.0, 3.0, 4.0];
-        let row = matrix.get_row(0);
-        assert_eq!(row, vec2);
+        assert_eq!(*running.mean().get(0, 0), 1.75);
     }
 
     #[test]
-    fn set_row_2() {
-        let mut matrix = Matrix::new(3, 4, 5.0);
-        let vec1 = vec![1.0, 2.0, 3.0, 4.0];
-        matrix.set_row(2, vec1);
+    fn eig_symmetric_3() {
+        let nan_diagonal = Matrix::from_rows(vec![vec![f64::NAN, 0.0], vec![0.0, 1.0]]);
+        let infinite = Matrix::from_rows(vec![vec![1.0, f64::INFINITY], vec![f64::INFINITY, 1.0]]);
 
-        let vec2 = vec![1.0, 2.0, 3.0, 4.0];
-        let row = matrix.get_row(2);
-        assert_eq!(row, vec2);
+        assert_eq!(nan_diagonal.eig_symmetric(), None);
+        assert_eq!(infinite.eig_symmetric(), None);
     }
 
     #[test]
-    fn set_column_1() {
-        let mut matrix = Matrix::new(3, 4, 5.0);
-        let vec1 = vec![1.0, 2.0, 3.0];
-        matrix.set_column(0, vec1);
+    #[should_panic]
+    fn sum_two_matrix_4() {
+        // Same number of elements, different shapes
+        let matrix1 = Matrix::new(2, 3, 1.0);
+        let matrix2 = Matrix::new(3, 2, 1.0);
 
-        let vec2 = vec![1.0, 2.0, 3.0];
-        let column = matrix.get_column(0);
-        assert_eq!(column, vec2);
+        let _result = matrix1 + matrix2;
     }
 
     #[test]
-    fn set_column_2() {
-        let mut matrix = Matrix::new(3, 4, 5.0);
-        let vec1 = vec![1.0, 2.0, 3.0];
-        matrix.set_column(3, vec1);
+    #[should_panic]
+    fn substract_two_matrix_4() {
+        let matrix1 = Matrix::new(2, 3, 1.0);
+        let matrix2 = Matrix::new(3, 2, 1.0);
 
-        let vec2 = vec![1.0, 2.0, 3.0];
-        let column = matrix.get_column(3);
-        assert_eq!(column, vec2);
+        let _result = matrix1 - matrix2;
     }
 
     #[test]
-    fn get_diagonal_1() {
-        let mut matrix = Matrix::new(3, 3, 8.0);
-        matrix.set(0, 0, 1.0);
-        matrix.set(1, 1, 2.0);
-        matrix.set(2, 2, 3.0);
+    fn cholesky_4() {
+        // Singular positive semi-definite matrices are rejected no matter
+        // which way the last pivot rounds
+        let matrix1 = Matrix::from_rows(vec![vec![2.0, 2.0], vec![2.0, 2.0]]);
+        let matrix2 = Matrix::from_rows(vec![vec![3.0, 3.0], vec![3.0, 3.0]]);
 
-        let vec1 = vec![1.0, 2.0, 3.0];
-        assert_eq!(matrix.get_diagonal(), vec1);
+        assert_eq!(matrix1.cholesky(), None);
+        assert_eq!(matrix2.cholesky(), None);
     }
 
     #[test]
-    fn get_diagonal_2() {
-        let mut matrix = Matrix::new(4, 4, 8.0);
-        matrix.set(0, 0, 1.0);
-        matrix.set(1, 1, 2.0);
-        matrix.set(2, 2, 3.0);
-        matrix.set(3, 3, 4.0);
+    fn projection_residual_3() {
+        // A column of scale 1e-9 still spans its direction
+        let matrix = Matrix::from_rows(vec![vec![1.0, 0.0], vec![0.0, 1e-9]]);
 
-        let vec1 = vec![1.0, 2.0, 3.0, 4.0];
-        assert_eq!(matrix.get_diagonal(), vec1);
+        let residual = matrix.projection_residual(&[1.0, 2.0]);
+        assert!(residual.abs() < 1e-12);
+
+        let matrix = Matrix::from_rows(vec![vec![1e-9, 0.0], vec![0.0, 0.0], vec![0.0, 1e-9]]);
+        let residual = matrix.projection_residual(&[1.0, 2.0, 3.0]);
+        assert!((residual - 2.0).abs() < 1e-12);
     }
 
     #[test]
-    fn get_determinant_1() {
-        let mut matrix = Matrix::new(2, 2, 0.0);
-        matrix.set(0, 0, 2.0);
-        matrix.set(1, 0, 4.0);
-        matrix.set(0, 1, 3.0);
-        matrix.set(1, 1, 5.0);
+    fn eig_symmetric_4() {
+        // Entries whose squares overflow or underflow
+        for magnitude in [1e200, 1e-200] {
+            let matrix = Matrix::new(2, 2, magnitude);
 
-        assert_eq!(matrix.get_determinant(), -2.0);
+            let (values, _vectors) = matrix.eig_symmetric().unwrap();
+
+            assert!(values[0].abs() < 1e-12 * magnitude);
+            assert!((values[1] - 2.0 * magnitude).abs() < 1e-12 * magnitude);
+        }
+
+        let (values, vectors) = Matrix::<f64>::zeros(2, 2).eig_symmetric().unwrap();
+        assert_eq!(values, vec![0.0, 0.0]);
+        assert_eq!(vectors, Matrix::identity(2));
     }
 
     #[test]
-    fn get_determinant_2() {
-        let mut matrix = Matrix::new(3, 3, 0.0);
-        matrix.set(0, 0, 3.0);
-        matrix.set(1, 0, 4.0);
-        matrix.set(2, 0, 7.0);
-        matrix.set(0, 1, 2.0);
-        matrix.set(1, 1, 5.0);
-        matrix.set(2, 1, 8.0);
-        matrix.set(0, 2, 1.0);
-        matrix.set(1, 2, 6.0);
-        matrix.set(2, 2, 9.0);
+    fn sqrt_spd_3() {
+        let matrix = Matrix::from_rows(vec![vec![2e200, 1e200], vec![1e200, 2e200]]);
 
-        assert_eq!(matrix.get_determinant(), 0.0);
+        let root = matrix.sqrt_spd().unwrap();
+        let square = root.checked_mul(&root).unwrap();
+
+        for i in 0..2 {
+            for k in 0..2 {
+                assert!((square.get(i, k) - matrix.get(i, k)).abs() < 1e-12 * 1e200);
+            }
+        }
     }
 
     #[test]
-    fn get_determinant_3() {
-        let mut matrix = Matrix::new(3, 3, 0.0);
-        matrix.set(0, 0, 0.0);
-        matrix.set(1, 0, 1.0);
-        matrix.set(2, 0, 2.0);
-        matrix.set(0, 1, 2.0);
-        matrix.set(1, 1, 3.0);
-        matrix.set(2, 1, 1.0);
-        matrix.set(0, 2, 1.0);
-        matrix.set(1, 2, 4.0);
-        matrix.set(2, 2, 3.0);
+    fn condition_number_svd_4() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 0.0], vec![0.0, 1e-8]]);
 
-        assert_eq!(matrix.get_determinant(), 5.0);
+        let condition = matrix.condition_number_svd().unwrap();
+        assert!((condition - 1e8).abs() < 1e-4);
+
+        // Rank 2, third row is the sum of the first two
+        let matrix = Matrix::from_rows(vec![
+            vec![0.3, 1.7, 2.0],
+            vec![1.1, -0.4, 0.7],
+            vec![1.4, 1.3, 2.7],
+        ]);
+        assert_eq!(matrix.condition_number_svd(), None);
     }
 
     #[test]
-    fn get_determinant_4() {
-        let mut matrix = Matrix::new(3, 3, 0.0);
-        matrix.set(0, 0, 2.0);
-        matrix.set(1, 0, 0.0);
-        matrix.set(2, 0, 1.0);
-        matrix.set(0, 1, 3.0);
-        matrix.set(1, 1, 4.0);
-        matrix.set(2, 1, 0.0);
-        matrix.set(0, 2, 5.0);
-        matrix.set(1, 2, 1.0);
-        matrix.set(2, 2, 6.0);
+    #[should_panic]
+    fn inertia_4() {
+        let matrix = Matrix::from_rows(vec![vec![1.0, 5.0], vec![0.0, 1.0]]);
 
-        assert_eq!(matrix.get_determinant(), 31.0);
+        let _counts = matrix.inertia(1e-12);
     }
 }